@@ -0,0 +1,175 @@
+use std::process::Command;
+
+use chrono::Utc;
+
+use crate::models::{GpuDataPoint, GpuInfo, SupportedMetrics, Vendor};
+use crate::vendor::{trim_history, GpuBackend};
+
+/// Reads AMD GPUs via `rocm-smi --showuse --showmeminfo vram --showtemp --json`,
+/// falling back to the amdgpu sysfs nodes when rocm-smi isn't installed.
+pub struct AmdBackend;
+
+impl AmdBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for AmdBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GpuBackend for AmdBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now();
+
+        if let Ok(output) = Command::new("rocm-smi")
+            .arg("--showuse")
+            .arg("--showmeminfo")
+            .arg("vram")
+            .arg("--showtemp")
+            .arg("--json")
+            .output()
+        {
+            if output.status.success() {
+                poll_rocm_smi(gpus, &output.stdout, now);
+                trim_history(gpus, now);
+                return Ok(());
+            }
+        }
+
+        poll_sysfs(gpus, now)?;
+        trim_history(gpus, now);
+        Ok(())
+    }
+}
+
+/// rocm-smi's `--json` output is a flat map keyed by `"card0"`, `"card1"`, ...
+/// with string values for every field. Parsed by hand, line by line, one card
+/// section at a time.
+fn poll_rocm_smi(gpus: &mut Vec<GpuInfo>, stdout: &[u8], now: chrono::DateTime<Utc>) {
+    let Ok(text) = std::str::from_utf8(stdout) else {
+        return;
+    };
+
+    let mut card_index = 0;
+    let mut in_card = false;
+    let mut gpu_util_raw = None;
+    let mut mem_used_raw = None;
+    let mut mem_total_raw = None;
+    let mut temperature_raw = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("\"card") && trimmed.ends_with('{') {
+            in_card = true;
+            gpu_util_raw = None;
+            mem_used_raw = None;
+            mem_total_raw = None;
+            temperature_raw = None;
+            continue;
+        }
+
+        if let Some(value) = extract_json_number(trimmed, "GPU use (%)") {
+            gpu_util_raw = Some(value);
+        } else if let Some(value) = extract_json_number(trimmed, "VRAM Total Used Memory (B)") {
+            mem_used_raw = Some(value / 1_000_000.0);
+        } else if let Some(value) = extract_json_number(trimmed, "VRAM Total Memory (B)") {
+            mem_total_raw = Some(value / 1_000_000.0);
+        } else if let Some(value) = extract_json_number(trimmed, "Temperature (Sensor edge) (C)") {
+            temperature_raw = Some(value);
+        }
+
+        // Only a card section's closing brace ends a device; the outer one doesn't.
+        if in_card && trimmed.starts_with('}') {
+            in_card = false;
+            let supported = SupportedMetrics {
+                utilization: gpu_util_raw.is_some(),
+                memory: mem_used_raw.is_some() && mem_total_raw.is_some(),
+                temperature: temperature_raw.is_some(),
+                power: false,
+                fan: false,
+                clocks: false,
+                pcie: false,
+            };
+
+            let data_point = GpuDataPoint {
+                timestamp: now,
+                gpu_util: gpu_util_raw.unwrap_or(0.0),
+                memory_used: mem_used_raw.unwrap_or(0.0),
+                memory_total: mem_total_raw.unwrap_or(0.0),
+                temperature: temperature_raw.unwrap_or(0.0),
+                power_usage: 0.0,
+                fan_speed: None,
+                sm_clock: None,
+                mem_clock: None,
+                pcie_tx: None,
+                pcie_rx: None,
+            };
+
+            while gpus.len() <= card_index {
+                gpus.push(GpuInfo::new(format!("AMD GPU {}", gpus.len()), Vendor::Amd));
+            }
+            gpus[card_index].supported = supported;
+            gpus[card_index].data_points.push_back(data_point);
+
+            card_index += 1;
+        }
+    }
+}
+
+fn extract_json_number(line: &str, key: &str) -> Option<f64> {
+    let rest = line.strip_prefix(&format!("\"{key}\": \""))?;
+    let value = rest.trim_end_matches("\",").trim_end_matches('"');
+    value.parse().ok()
+}
+
+/// Best-effort fallback: the amdgpu driver exposes live utilization at
+/// `/sys/class/drm/card*/device/gpu_busy_percent` even without rocm-smi.
+fn poll_sysfs(gpus: &mut Vec<GpuInfo>, now: chrono::DateTime<Utc>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut card_index = 0;
+    for entry in std::fs::read_dir("/sys/class/drm")?.flatten() {
+        let busy_path = entry.path().join("device/gpu_busy_percent");
+        let Ok(busy) = std::fs::read_to_string(&busy_path) else {
+            continue;
+        };
+        let gpu_util_raw = busy.trim().parse::<f64>().ok();
+
+        let supported = SupportedMetrics {
+            utilization: gpu_util_raw.is_some(),
+            memory: false,
+            temperature: false,
+            power: false,
+            fan: false,
+            clocks: false,
+            pcie: false,
+        };
+
+        let data_point = GpuDataPoint {
+            timestamp: now,
+            gpu_util: gpu_util_raw.unwrap_or(0.0),
+            memory_used: 0.0,
+            memory_total: 0.0,
+            temperature: 0.0,
+            power_usage: 0.0,
+            fan_speed: None,
+            sm_clock: None,
+            mem_clock: None,
+            pcie_tx: None,
+            pcie_rx: None,
+        };
+
+        while gpus.len() <= card_index {
+            gpus.push(GpuInfo::new(format!("AMD GPU {}", gpus.len()), Vendor::Amd));
+        }
+        gpus[card_index].supported = supported;
+        gpus[card_index].data_points.push_back(data_point);
+
+        card_index += 1;
+    }
+
+    Ok(())
+}
@@ -1,32 +1,214 @@
-use std::time::Instant;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
-use crate::models::GpuInfo;
-use crate::nvidia::fetch_gpu_data;
+use chrono::{TimeDelta, Utc};
+
+use crate::models::{GpuInfo, ProcessInfo, ProcessSorting, Vendor};
+use crate::replay::ReplayBackend;
+use crate::storage::Store;
+use crate::vendor::{self, GpuBackend, MAX_HISTORY_MINUTES};
+
+/// A second `d` keypress within this window arms the kill-confirmation dialog.
+const DOUBLE_KEY_WINDOW_MS: u128 = 500;
+
+const MIN_HISTORY_WINDOW_MINUTES: i64 = 5;
+const HISTORY_STEP_MINUTES: i64 = 30;
+
+/// How often `update_gpu_data` prunes rows older than [`MAX_HISTORY_MINUTES`]
+/// from the history DB. Pruning on every tick would mean an extra DELETE scan
+/// once a second for no benefit, since nothing expires faster than this.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// How many recent samples the frame/poll-time ring buffers keep.
+const PROFILER_HISTORY_LEN: usize = 120;
+
+/// Rolling min/avg/max for either the render loop or the data-poll call,
+/// computed on demand from the ring buffer so nothing is kept pre-aggregated.
+#[derive(Clone, Copy, Debug)]
+pub struct TimingStats {
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+fn timing_stats(samples: &VecDeque<f64>) -> Option<TimingStats> {
+    if samples.is_empty() {
+        return None;
+    }
+    let min_ms = samples.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max_ms = samples.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let avg_ms = samples.iter().sum::<f64>() / samples.len() as f64;
+    Some(TimingStats { min_ms, avg_ms, max_ms })
+}
+
+fn push_sample(samples: &mut VecDeque<f64>, value_ms: f64) {
+    samples.push_back(value_ms);
+    while samples.len() > PROFILER_HISTORY_LEN {
+        samples.pop_front();
+    }
+}
 
 /// Main application state
 pub struct App {
     pub gpus: Vec<GpuInfo>,
     pub last_update: Instant,
     pub frame_count: u64,
+    /// GPU whose process panel keybindings (sort/select/kill) apply.
+    pub selected_gpu: usize,
+    pub process_sort: ProcessSorting,
+    pub process_sort_reverse: bool,
+    pub selected_process: usize,
+    /// PID awaiting confirmation in the `dd` kill dialog.
+    pub pending_kill: Option<u32>,
+    last_d_press: Option<Instant>,
+    backend: Box<dyn GpuBackend>,
+    /// Optional SQLite-backed history; absent if the database couldn't be opened.
+    store: Option<Store>,
+    /// Last time old rows were pruned from the history DB.
+    last_prune: Instant,
+    pub history_window: TimeDelta,
+    /// When set, `should_update` stops polling so the current snapshot holds still.
+    pub is_frozen: bool,
+    pub show_help: bool,
+    /// Recent render durations in milliseconds, newest at the back.
+    frame_times: VecDeque<f64>,
+    /// Recent `update_gpu_data` durations in milliseconds, newest at the back.
+    poll_times: VecDeque<f64>,
+    pub show_profiler: bool,
 }
 
 impl App {
-    pub fn new() -> Self {
+    /// Builds an `App` that auto-detects every installed GPU vendor. Pass
+    /// `forced_vendor` to restrict discovery to a single one (`--vendor`).
+    pub fn new(forced_vendor: Option<Vendor>) -> Self {
+        Self::with_backend(vendor::build_backend(forced_vendor))
+    }
+
+    /// Builds an `App` that replays a previously recorded history database
+    /// (`--replay <file>`) instead of polling live hardware. Persistence is
+    /// left disabled so replaying a file never writes back into it.
+    pub fn replay(path: &str) -> rusqlite::Result<Self> {
+        Ok(Self::with_backend(Box::new(ReplayBackend::open(path)?)))
+    }
+
+    fn with_backend(backend: Box<dyn GpuBackend>) -> Self {
         App {
             gpus: Vec::new(),
             last_update: Instant::now(),
             frame_count: 0,
+            selected_gpu: 0,
+            process_sort: ProcessSorting::Memory,
+            process_sort_reverse: true,
+            selected_process: 0,
+            pending_kill: None,
+            last_d_press: None,
+            backend,
+            store: None,
+            last_prune: Instant::now(),
+            history_window: TimeDelta::try_minutes(60).unwrap_or_default(),
+            is_frozen: false,
+            show_help: false,
+            frame_times: VecDeque::new(),
+            poll_times: VecDeque::new(),
+            show_profiler: false,
         }
     }
 
-    /// Fetch GPU data from nvidia-smi
+    /// Opens the SQLite history database at `path`. Persistence stays
+    /// disabled (silently) if this is never called or fails.
+    pub fn open_store(&mut self, path: &str) -> rusqlite::Result<()> {
+        self.store = Some(Store::open(path)?);
+        Ok(())
+    }
+
+    pub fn set_history_window(&mut self, window: TimeDelta) {
+        self.history_window = window;
+        self.reload_history();
+    }
+
+    /// Widens the visible history range, paging older rows back in from the DB.
+    pub fn widen_history(&mut self) {
+        let max = TimeDelta::try_minutes(MAX_HISTORY_MINUTES).unwrap_or_default();
+        let step = TimeDelta::try_minutes(HISTORY_STEP_MINUTES).unwrap_or_default();
+        self.set_history_window((self.history_window + step).min(max));
+    }
+
+    /// Narrows the visible history range back down.
+    pub fn narrow_history(&mut self) {
+        let min = TimeDelta::try_minutes(MIN_HISTORY_WINDOW_MINUTES).unwrap_or_default();
+        let step = TimeDelta::try_minutes(HISTORY_STEP_MINUTES).unwrap_or_default();
+        self.set_history_window((self.history_window - step).max(min));
+    }
+
+    /// Replaces each GPU's in-memory history with what the DB has for the
+    /// current `history_window`. Call once after the first poll (so GPU
+    /// indices are known) and again whenever the window changes.
+    pub fn reload_history(&mut self) {
+        let Some(store) = &self.store else { return };
+        for (idx, gpu) in self.gpus.iter_mut().enumerate() {
+            if let Ok(history) = store.load_history(idx, self.history_window) {
+                gpu.data_points = history.into();
+            }
+        }
+    }
+
+    /// Fetch GPU data from the active backend (the detected vendor collectors,
+    /// or a replayed recording)
     pub fn update_gpu_data(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        fetch_gpu_data(&mut self.gpus)
+        let poll_started = Instant::now();
+        let result = self.backend.poll(&mut self.gpus);
+        push_sample(&mut self.poll_times, poll_started.elapsed().as_secs_f64() * 1000.0);
+        result?;
+        if let Some(store) = self.store.as_ref() {
+            for (idx, gpu) in self.gpus.iter().enumerate() {
+                if let Some(point) = gpu.data_points.back() {
+                    let _ = store.insert(idx, point, gpu.vendor);
+                }
+            }
+            // Widening/narrowing already calls `reload_history` to page rows
+            // in from the DB; on a plain tick there's nothing new to page in
+            // beyond what the backend just appended, so only trim the front
+            // of each deque back down to `history_window` instead of
+            // re-running the full query every second.
+            self.trim_to_window();
+            self.maybe_prune();
+        }
+        Ok(())
     }
 
-    /// Check if update is needed (every second)
+    /// Drops in-memory samples older than `history_window` without touching
+    /// the DB. Cheap counterpart to `reload_history`, used on every tick while
+    /// a history DB is open.
+    fn trim_to_window(&mut self) {
+        let cutoff = Utc::now() - self.history_window;
+        for gpu in &mut self.gpus {
+            while let Some(front) = gpu.data_points.front() {
+                if front.timestamp < cutoff {
+                    gpu.data_points.pop_front();
+                } else {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Deletes history rows older than [`MAX_HISTORY_MINUTES`] from the DB,
+    /// at most once per [`PRUNE_INTERVAL`], so a long-running session doesn't
+    /// grow `gpu_history.db` without bound.
+    fn maybe_prune(&mut self) {
+        if self.last_prune.elapsed() < PRUNE_INTERVAL {
+            return;
+        }
+        self.last_prune = Instant::now();
+        if let Some(store) = self.store.as_ref() {
+            let cutoff = Utc::now() - TimeDelta::try_minutes(MAX_HISTORY_MINUTES).unwrap_or_default();
+            let _ = store.prune(cutoff);
+        }
+    }
+
+    /// Check if update is needed (every second); frozen apps never update.
     pub fn should_update(&self) -> bool {
-        self.last_update.elapsed().as_secs() >= 1
+        !self.is_frozen && self.last_update.elapsed().as_secs() >= 1
     }
 
     /// Mark update as complete
@@ -38,10 +220,133 @@ impl App {
     pub fn tick(&mut self) {
         self.frame_count += 1;
     }
+
+    pub fn toggle_freeze(&mut self) {
+        self.is_frozen = !self.is_frozen;
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    pub fn toggle_profiler(&mut self) {
+        self.show_profiler = !self.show_profiler;
+    }
+
+    /// Records the wall-clock time a full `terminal.draw` call took, for the
+    /// frame-time overlay.
+    pub fn record_frame_time(&mut self, duration: Duration) {
+        push_sample(&mut self.frame_times, duration.as_secs_f64() * 1000.0);
+    }
+
+    /// Rolling min/avg/max render time, plus the FPS implied by the average.
+    pub fn frame_stats(&self) -> Option<(TimingStats, f64)> {
+        let stats = timing_stats(&self.frame_times)?;
+        let fps = if stats.avg_ms > 0.0 { 1000.0 / stats.avg_ms } else { 0.0 };
+        Some((stats, fps))
+    }
+
+    /// Rolling min/avg/max time spent in the last `update_gpu_data` calls,
+    /// i.e. how long the NVML/nvidia-smi/etc. backend took to respond.
+    pub fn poll_stats(&self) -> Option<TimingStats> {
+        timing_stats(&self.poll_times)
+    }
+
+    /// Clears all in-memory history (the `Ctrl-r` reset). Persisted samples
+    /// in the history DB, if any, are left untouched.
+    pub fn reset_history(&mut self) {
+        for gpu in &mut self.gpus {
+            gpu.data_points.clear();
+        }
+    }
+
+    /// Moves panel focus to the next GPU, expanding its panel.
+    pub fn focus_next_gpu(&mut self) {
+        if !self.gpus.is_empty() {
+            self.selected_gpu = (self.selected_gpu + 1) % self.gpus.len();
+            self.selected_process = 0;
+        }
+    }
+
+    /// Moves panel focus to the previous GPU, expanding its panel.
+    pub fn focus_prev_gpu(&mut self) {
+        if !self.gpus.is_empty() {
+            let len = self.gpus.len();
+            self.selected_gpu = (self.selected_gpu + len - 1) % len;
+            self.selected_process = 0;
+        }
+    }
+
+    /// Processes of the selected GPU, ordered by the current sort column.
+    pub fn sorted_processes(&self) -> Vec<ProcessInfo> {
+        let mut processes: Vec<&ProcessInfo> = self
+            .gpus
+            .get(self.selected_gpu)
+            .map(|gpu| gpu.processes.iter().collect())
+            .unwrap_or_default();
+        self.process_sort.sort(&mut processes, self.process_sort_reverse);
+        processes.into_iter().cloned().collect()
+    }
+
+    pub fn set_process_sort(&mut self, sort: ProcessSorting) {
+        self.process_sort = sort;
+        self.selected_process = 0;
+    }
+
+    pub fn toggle_process_sort_reverse(&mut self) {
+        self.process_sort_reverse = !self.process_sort_reverse;
+    }
+
+    pub fn select_next_process(&mut self) {
+        let count = self.sorted_processes().len();
+        if count > 0 {
+            self.selected_process = (self.selected_process + 1) % count;
+        }
+    }
+
+    pub fn select_prev_process(&mut self) {
+        let count = self.sorted_processes().len();
+        if count > 0 {
+            self.selected_process = (self.selected_process + count - 1) % count;
+        }
+    }
+
+    /// Registers a `d` keypress; a second one within [`DOUBLE_KEY_WINDOW_MS`]
+    /// arms the kill-confirmation dialog for the currently selected process.
+    pub fn handle_d_key(&mut self) {
+        let now = Instant::now();
+        let is_repeat = self
+            .last_d_press
+            .map(|prev| now.duration_since(prev).as_millis() < DOUBLE_KEY_WINDOW_MS)
+            .unwrap_or(false);
+
+        if is_repeat {
+            self.last_d_press = None;
+            if let Some(proc) = self.sorted_processes().get(self.selected_process) {
+                self.pending_kill = Some(proc.pid);
+            }
+        } else {
+            self.last_d_press = Some(now);
+        }
+    }
+
+    /// Sends SIGKILL to the pending PID, if any, and dismisses the dialog.
+    pub fn confirm_kill(&mut self) {
+        if let Some(pid) = self.pending_kill.take() {
+            let _ = std::process::Command::new("kill")
+                .arg("-9")
+                .arg(pid.to_string())
+                .output();
+        }
+    }
+
+    pub fn cancel_kill(&mut self) {
+        self.pending_kill = None;
+    }
 }
 
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(None)
     }
 }
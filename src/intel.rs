@@ -0,0 +1,82 @@
+use chrono::Utc;
+
+use crate::models::{GpuDataPoint, GpuInfo, SupportedMetrics, Vendor};
+use crate::vendor::{trim_history, GpuBackend};
+
+/// Best-effort Intel GPU collector. Intel doesn't ship an equivalent to
+/// nvidia-smi/rocm-smi on most systems, so this only reads what the i915/xe
+/// driver already exposes over sysfs — no utilization or memory, just
+/// presence and whatever clock info is published.
+pub struct IntelBackend;
+
+impl IntelBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for IntelBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GpuBackend for IntelBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now();
+        let mut card_index = 0;
+
+        for entry in std::fs::read_dir("/sys/class/drm")?.flatten() {
+            let vendor_path = entry.path().join("device/vendor");
+            let Ok(vendor_id) = std::fs::read_to_string(&vendor_path) else {
+                continue;
+            };
+            if vendor_id.trim() != "0x8086" {
+                continue;
+            }
+
+            let sm_clock = std::fs::read_to_string(entry.path().join("device/gt_cur_freq_mhz"))
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok());
+            let max_sm_clock = std::fs::read_to_string(entry.path().join("device/gt_max_freq_mhz"))
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok());
+
+            let supported = SupportedMetrics {
+                utilization: false,
+                memory: false,
+                temperature: false,
+                power: false,
+                fan: false,
+                clocks: sm_clock.is_some(),
+                pcie: false,
+            };
+
+            let data_point = GpuDataPoint {
+                timestamp: now,
+                gpu_util: 0.0,
+                memory_used: 0.0,
+                memory_total: 0.0,
+                temperature: 0.0,
+                power_usage: 0.0,
+                fan_speed: None,
+                sm_clock,
+                mem_clock: None,
+                pcie_tx: None,
+                pcie_rx: None,
+            };
+
+            while gpus.len() <= card_index {
+                gpus.push(GpuInfo::new(format!("Intel GPU {}", gpus.len()), Vendor::Intel));
+            }
+            gpus[card_index].supported = supported;
+            gpus[card_index].max_sm_clock = max_sm_clock;
+            gpus[card_index].data_points.push_back(data_point);
+
+            card_index += 1;
+        }
+
+        trim_history(gpus, now);
+        Ok(())
+    }
+}
@@ -1,425 +1,98 @@
-use chrono::{DateTime, Duration, Utc};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Gauge, Paragraph, Sparkline},
-    Frame, Terminal,
-};
-use std::collections::VecDeque;
+mod amd;
+mod app;
+mod intel;
+mod models;
+mod nvidia;
+mod replay;
+mod storage;
+mod theme;
+mod ui;
+mod vendor;
+
+use app::App;
+use chrono::TimeDelta;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use models::{ProcessSorting, Vendor};
+use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
-use std::process::Command;
-use std::time::Instant;
-
-// Приглушённая хакерская цветовая схема
-const NEON_GREEN: Color = Color::Rgb(0, 160, 50);
-const NEON_CYAN: Color = Color::Rgb(0, 150, 160);
-const NEON_MAGENTA: Color = Color::Rgb(160, 60, 160);
-const NEON_YELLOW: Color = Color::Rgb(180, 160, 60);
-const NEON_RED: Color = Color::Rgb(180, 60, 60);
-const DARK_BG: Color = Color::Rgb(15, 15, 25);
-const MATRIX_GREEN: Color = Color::Rgb(30, 130, 30);
-const CYBER_BLUE: Color = Color::Rgb(60, 130, 180);
-
-#[derive(Clone, Debug)]
-struct GpuDataPoint {
-    timestamp: DateTime<Utc>,
-    gpu_util: f64,
-    memory_used: f64,
-    memory_total: f64,
-    temperature: f64,
-    power_usage: f64,
-}
-
-#[derive(Clone, Debug)]
-struct GpuInfo {
-    name: String,
-    data_points: VecDeque<GpuDataPoint>,
-}
-
-struct App {
-    gpus: Vec<GpuInfo>,
-    last_update: Instant,
-    frame_count: u64,
-}
-
-impl App {
-    fn new() -> Self {
-        App {
-            gpus: Vec::new(),
-            last_update: Instant::now(),
-            frame_count: 0,
-        }
+use theme::Theme;
+use ui::{ProcessView, ProfilerView, RenderContext};
+
+const DEFAULT_HISTORY_DB: &str = "gpu_history.db";
+
+/// Parses a `--history <duration>` value like `2h`, `30m`, `45s`, `1d`.
+fn parse_history_duration(input: &str) -> Option<TimeDelta> {
+    let split_at = input.len().checked_sub(1)?;
+    let (value, suffix) = input.split_at(split_at);
+    let amount: i64 = value.parse().ok()?;
+    match suffix {
+        "s" => TimeDelta::try_seconds(amount),
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        _ => None,
     }
+}
 
-    fn fetch_gpu_data(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let output = Command::new("nvidia-smi")
-            .arg("--query-gpu=index,name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw")
-            .arg("--format=csv,noheader,nounits")
-            .output()?;
-
-        let output_str = String::from_utf8(output.stdout)?;
-        let now = Utc::now();
-
-        for (idx, line) in output_str.lines().enumerate() {
-            let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-            if parts.len() >= 7 {
-                let gpu_index: usize = parts[0].parse().unwrap_or(idx);
-                let name = parts[1].to_string();
-                let gpu_util: f64 = parts[2].parse().unwrap_or(0.0);
-                let memory_used: f64 = parts[3].parse().unwrap_or(0.0);
-                let memory_total: f64 = parts[4].parse().unwrap_or(0.0);
-                let temperature: f64 = parts[5].parse().unwrap_or(0.0);
-                let power_usage: f64 = parts[6].parse().unwrap_or(0.0);
-
-                let data_point = GpuDataPoint {
-                    timestamp: now,
-                    gpu_util,
-                    memory_used,
-                    memory_total,
-                    temperature,
-                    power_usage,
-                };
-
-                while self.gpus.len() <= gpu_index {
-                    self.gpus.push(GpuInfo {
-                        name: format!("GPU {}", self.gpus.len()),
-                        data_points: VecDeque::new(),
-                    });
-                }
-
-                self.gpus[gpu_index].name = name;
-                self.gpus[gpu_index].data_points.push_back(data_point);
-
-                // Keep last 60 minutes of data
-                let cutoff = now - Duration::minutes(60);
-                while let Some(front) = self.gpus[gpu_index].data_points.front() {
-                    if front.timestamp < cutoff {
-                        self.gpus[gpu_index].data_points.pop_front();
-                    } else {
-                        break;
-                    }
-                }
+/// Scans argv for `--history <duration>`, falling back to the 60-minute default.
+fn history_window_from_args() -> TimeDelta {
+    let default = TimeDelta::try_minutes(60).unwrap_or_default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--history" {
+            if let Some(value) = args.next().and_then(|v| parse_history_duration(&v)) {
+                return value;
             }
         }
-
-        Ok(())
     }
+    default
+}
 
-    fn get_data(&self, gpu_idx: usize) -> Vec<GpuDataPoint> {
-        if gpu_idx >= self.gpus.len() {
-            return Vec::new();
+/// Scans argv for `--vendor <nvidia|amd|intel>`, pinning GPU discovery to
+/// that vendor instead of auto-detecting every one installed.
+fn vendor_from_args() -> Option<Vendor> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--vendor" {
+            return match args.next().as_deref() {
+                Some("nvidia") => Some(Vendor::Nvidia),
+                Some("amd") => Some(Vendor::Amd),
+                Some("intel") => Some(Vendor::Intel),
+                _ => None,
+            };
         }
-        self.gpus[gpu_idx].data_points.iter().cloned().collect()
     }
+    None
+}
 
-    fn render(&mut self, f: &mut Frame) {
-        self.frame_count += 1;
-
-        // Основной контейнер с темным фоном
-        let main_block = Block::default().style(Style::default().bg(DARK_BG));
-        f.render_widget(main_block, f.size());
-
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([
-                Constraint::Length(5), // Header
-                Constraint::Min(0),    // GPU panels
-            ])
-            .split(f.size());
-
-        self.render_header(f, chunks[0]);
-
-        if self.gpus.is_empty() {
-            self.render_no_gpu(f, chunks[1]);
-            return;
-        }
-
-        // Динамическое распределение места для GPU
-        let gpu_count = self.gpus.len();
-        let gpu_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                (0..gpu_count)
-                    .map(|_| Constraint::Ratio(1, gpu_count as u32))
-                    .collect::<Vec<_>>(),
-            )
-            .split(chunks[1]);
-
-        for (gpu_idx, _) in self.gpus.iter().enumerate() {
-            self.render_gpu(f, gpu_idx, gpu_chunks[gpu_idx]);
+/// Scans argv for `--replay <file>`, selecting a recorded-history playback
+/// session instead of live GPU polling.
+fn replay_path_from_args() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--replay" {
+            return args.next();
         }
     }
+    None
+}
 
-    fn render_header(&self, f: &mut Frame, area: Rect) {
-        let now = Utc::now();
-        let uptime = self
-            .gpus
-            .first()
-            .and_then(|g| g.data_points.front())
-            .map(|dp| now - dp.timestamp)
-            .unwrap_or(Duration::zero());
-
-        let glitch_char = if self.frame_count % 10 < 2 {
-            "█"
-        } else {
-            " "
-        };
-
-        let header_text = vec![
-            Line::from(vec![Span::styled(
-                "╔══════════════════════════════════════════════════════════════╗",
-                Style::default().fg(NEON_GREEN),
-            )]),
-            Line::from(vec![
-                Span::styled("║  ", Style::default().fg(NEON_GREEN)),
-                Span::styled(glitch_char, Style::default().fg(NEON_MAGENTA)),
-                Span::styled(
-                    " GPU MONITOR ",
-                    Style::default().fg(NEON_GREEN).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("v1.0 ", Style::default().fg(CYBER_BLUE)),
-                Span::styled("│ ", Style::default().fg(NEON_GREEN)),
-                Span::styled(
-                    format!("{}", now.format("%H:%M:%S")),
-                    Style::default().fg(NEON_YELLOW),
-                ),
-                Span::styled(" │ ", Style::default().fg(NEON_GREEN)),
-                Span::styled(
-                    format!(
-                        "UPTIME: {:02}:{:02}:{:02}",
-                        uptime.num_hours(),
-                        uptime.num_minutes() % 60,
-                        uptime.num_seconds() % 60
-                    ),
-                    Style::default().fg(NEON_CYAN),
-                ),
-                Span::styled(" │ ", Style::default().fg(NEON_GREEN)),
-                Span::styled(
-                    format!("GPUs: {}", self.gpus.len()),
-                    Style::default().fg(NEON_MAGENTA),
-                ),
-                Span::styled(format!("{:>2}║", ""), Style::default().fg(NEON_GREEN)),
-            ]),
-            Line::from(vec![Span::styled(
-                "╚══════════════════════════════════════════════════════════════╝",
-                Style::default().fg(NEON_GREEN),
-            )]),
-        ];
-
-        let header = Paragraph::new(header_text)
-            .style(Style::default().bg(DARK_BG))
-            .alignment(Alignment::Left);
-        f.render_widget(header, area);
-    }
-
-    fn render_no_gpu(&self, f: &mut Frame, area: Rect) {
-        let blink = if self.frame_count % 20 < 10 {
-            "█"
-        } else {
-            " "
-        };
-        let text = vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("  [", Style::default().fg(NEON_RED)),
-                Span::styled(
-                    "!",
-                    Style::default()
-                        .fg(NEON_YELLOW)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("] ", Style::default().fg(NEON_RED)),
-                Span::styled(
-                    "SCANNING FOR GPU DEVICES",
-                    Style::default().fg(NEON_RED).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(blink, Style::default().fg(NEON_GREEN)),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled(
-                "      Waiting for nvidia-smi response...",
-                Style::default().fg(Color::DarkGray),
-            )]),
-        ];
-
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Double)
-            .border_style(Style::default().fg(NEON_RED))
-            .style(Style::default().bg(DARK_BG));
-
-        let paragraph = Paragraph::new(text).block(block);
-        f.render_widget(paragraph, area);
-    }
-
-    fn render_gpu(&self, f: &mut Frame, gpu_idx: usize, area: Rect) {
-        let data = self.get_data(gpu_idx);
-
-        if data.is_empty() {
-            return;
+/// Scans argv for `--theme <name|path>`, falling back to the built-in
+/// "hacker" palette. An unknown name/unreadable path is reported and
+/// falls back rather than aborting startup.
+fn theme_from_args() -> Theme {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--theme" {
+            if let Some(value) = args.next() {
+                return Theme::load(&value).unwrap_or_else(|err| {
+                    eprintln!("warning: {err}, falling back to default theme");
+                    Theme::default()
+                });
+            }
         }
-
-        let latest = data.last().unwrap();
-
-        // Определяем цвет статуса по загрузке
-        let status_color = if latest.gpu_util > 90.0 {
-            NEON_RED
-        } else if latest.gpu_util > 50.0 {
-            NEON_YELLOW
-        } else {
-            NEON_GREEN
-        };
-
-        let temp_color = if latest.temperature > 80.0 {
-            NEON_RED
-        } else if latest.temperature > 60.0 {
-            NEON_YELLOW
-        } else {
-            NEON_CYAN
-        };
-
-        // GPU блок
-        let gpu_block = Block::default()
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(MATRIX_GREEN))
-            .title(vec![
-                Span::styled(" ◆ ", Style::default().fg(status_color)),
-                Span::styled(
-                    format!("GPU {} ", gpu_idx),
-                    Style::default().fg(NEON_GREEN).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled("│ ", Style::default().fg(MATRIX_GREEN)),
-                Span::styled(&self.gpus[gpu_idx].name, Style::default().fg(CYBER_BLUE)),
-                Span::styled(" ", Style::default()),
-            ])
-            .style(Style::default().bg(DARK_BG));
-
-        f.render_widget(gpu_block, area);
-
-        let inner = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([
-                Constraint::Length(2), // Stats line
-                Constraint::Length(3), // GPU Util bar
-                Constraint::Length(3), // Memory bar
-                Constraint::Min(3),    // Sparklines
-            ])
-            .split(area);
-
-        // Stats line
-        let stats_line = Line::from(vec![
-            Span::styled("  ┌─ ", Style::default().fg(MATRIX_GREEN)),
-            Span::styled("UTIL: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{:5.1}%", latest.gpu_util),
-                Style::default()
-                    .fg(status_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" │ ", Style::default().fg(MATRIX_GREEN)),
-            Span::styled("TEMP: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{:3.0}°C", latest.temperature),
-                Style::default().fg(temp_color).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(" │ ", Style::default().fg(MATRIX_GREEN)),
-            Span::styled("PWR: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{:6.1}W", latest.power_usage),
-                Style::default().fg(NEON_YELLOW),
-            ),
-            Span::styled(" │ ", Style::default().fg(MATRIX_GREEN)),
-            Span::styled("MEM: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(
-                format!("{:.0}/{:.0}MB", latest.memory_used, latest.memory_total),
-                Style::default().fg(NEON_MAGENTA),
-            ),
-            Span::styled(" ─┐", Style::default().fg(MATRIX_GREEN)),
-        ]);
-        let stats = Paragraph::new(stats_line).style(Style::default().bg(DARK_BG));
-        f.render_widget(stats, inner[0]);
-
-        // GPU Utilization bar
-        let util_label = format!("▓ GPU {:5.1}%", latest.gpu_util);
-        let util_gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .borders(Borders::NONE)
-                    .style(Style::default().bg(DARK_BG)),
-            )
-            .gauge_style(Style::default().fg(status_color).bg(Color::Rgb(20, 20, 30)))
-            .percent(latest.gpu_util as u16)
-            .label(Span::styled(
-                util_label,
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        f.render_widget(util_gauge, inner[1]);
-
-        // Memory bar
-        let mem_percent = (latest.memory_used / latest.memory_total) * 100.0;
-        let mem_label = format!("▓ MEM {:5.1}%", mem_percent);
-        let mem_gauge = Gauge::default()
-            .block(
-                Block::default()
-                    .borders(Borders::NONE)
-                    .style(Style::default().bg(DARK_BG)),
-            )
-            .gauge_style(Style::default().fg(NEON_MAGENTA).bg(Color::Rgb(20, 20, 30)))
-            .percent(mem_percent as u16)
-            .label(Span::styled(
-                mem_label,
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        f.render_widget(mem_gauge, inner[2]);
-
-        // Sparklines row
-        let spark_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(inner[3]);
-
-        // GPU Util sparkline
-        let util_data: Vec<u64> = data.iter().map(|dp| dp.gpu_util as u64).collect();
-        let util_sparkline = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::Rgb(40, 80, 40)))
-                    .title(Span::styled(" ◇ UTIL% ", Style::default().fg(NEON_GREEN)))
-                    .style(Style::default().bg(DARK_BG)),
-            )
-            .data(&util_data)
-            .style(Style::default().fg(MATRIX_GREEN))
-            .max(100);
-        f.render_widget(util_sparkline, spark_chunks[0]);
-
-        // Temperature sparkline
-        let temp_data: Vec<u64> = data.iter().map(|dp| dp.temperature as u64).collect();
-        let temp_sparkline = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::Rgb(80, 40, 40)))
-                    .title(Span::styled(" ◇ TEMP°C ", Style::default().fg(NEON_RED)))
-                    .style(Style::default().bg(DARK_BG)),
-            )
-            .data(&temp_data)
-            .style(Style::default().fg(NEON_RED))
-            .max(100);
-        f.render_widget(temp_sparkline, spark_chunks[1]);
     }
+    Theme::default()
 }
 
 #[tokio::main]
@@ -432,25 +105,105 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         crossterm::event::EnableMouseCapture
     )?;
 
-    let mut app = App::new();
+    let replay_path = replay_path_from_args();
+    let mut app = match &replay_path {
+        Some(path) => App::replay(path)?,
+        None => App::new(vendor_from_args()),
+    };
+    app.history_window = history_window_from_args();
+    let theme = theme_from_args();
+    if replay_path.is_none() {
+        if let Err(err) = app.open_store(DEFAULT_HISTORY_DB) {
+            eprintln!("warning: could not open GPU history database: {err}");
+        }
+    }
 
-    // Initial fetch
-    let _ = app.fetch_gpu_data();
+    // Initial fetch, then backfill from the history DB now that GPU indices are known.
+    let _ = app.update_gpu_data();
+    app.reload_history();
 
     loop {
         // Update data every second
-        if app.last_update.elapsed().as_secs() >= 1 {
-            let _ = app.fetch_gpu_data();
-            app.last_update = Instant::now();
+        if app.should_update() {
+            let _ = app.update_gpu_data();
+            app.mark_updated();
         }
 
-        terminal.draw(|f| app.render(f))?;
+        app.tick();
+        let frame_started = std::time::Instant::now();
+        let profiler_view = app.show_profiler.then(|| app.frame_stats()).flatten().map(|(frame, fps)| {
+            ProfilerView {
+                frame,
+                fps,
+                poll: app.poll_stats(),
+            }
+        });
+        terminal.draw(|f| {
+            let process_view = ProcessView {
+                selected_gpu: app.selected_gpu,
+                sort: app.process_sort,
+                sort_reverse: app.process_sort_reverse,
+                selected_process: app.selected_process,
+                pending_kill: app.pending_kill,
+            };
+            let ctx = RenderContext {
+                theme: &theme,
+                profiler: profiler_view.as_ref(),
+            };
+            ui::render(
+                f,
+                &app.gpus,
+                app.frame_count,
+                &process_view,
+                app.is_frozen,
+                app.show_help,
+                &ctx,
+            )
+        })?;
+        app.record_frame_time(frame_started.elapsed());
 
         if crossterm::event::poll(std::time::Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
+                    if app.pending_kill.is_some() {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Enter => app.confirm_kill(),
+                            KeyCode::Char('n') | KeyCode::Esc => app.cancel_kill(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app.show_help {
+                        match key.code {
+                            KeyCode::Char('?') | KeyCode::Esc => app.toggle_help(),
+                            KeyCode::Char('q') => break,
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                        app.reset_history();
+                        continue;
+                    }
+
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Char('p') => app.set_process_sort(ProcessSorting::Pid),
+                        KeyCode::Char('n') => app.set_process_sort(ProcessSorting::Name),
+                        KeyCode::Char('m') => app.set_process_sort(ProcessSorting::Memory),
+                        KeyCode::Char('r') => app.toggle_process_sort_reverse(),
+                        KeyCode::Down => app.select_next_process(),
+                        KeyCode::Up => app.select_prev_process(),
+                        KeyCode::Char('d') => app.handle_d_key(),
+                        KeyCode::Char(']') => app.widen_history(),
+                        KeyCode::Char('[') => app.narrow_history(),
+                        KeyCode::Char('f') => app.toggle_freeze(),
+                        KeyCode::Char('t') => app.toggle_profiler(),
+                        KeyCode::Char('?') => app.toggle_help(),
+                        KeyCode::Tab => app.focus_next_gpu(),
+                        KeyCode::BackTab => app.focus_prev_gpu(),
                         _ => {}
                     }
                 }
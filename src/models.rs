@@ -9,19 +9,139 @@ pub struct GpuDataPoint {
     pub memory_total: f64,
     pub temperature: f64,
     pub power_usage: f64,
+    /// Fan speed as a percentage of max, when the device/backend reports it.
+    pub fan_speed: Option<f64>,
+    /// SM/graphics clock in MHz, when the device/backend reports it.
+    pub sm_clock: Option<f64>,
+    /// Memory clock in MHz, when the device/backend reports it.
+    pub mem_clock: Option<f64>,
+    /// PCIe TX throughput in KB/s, when the device/backend reports it.
+    pub pcie_tx: Option<f64>,
+    /// PCIe RX throughput in KB/s, when the device/backend reports it.
+    pub pcie_rx: Option<f64>,
+}
+
+/// Whether a process holds the GPU open for compute (CUDA/OpenCL) or
+/// graphics (rendering/display) work; nvidia-smi shows this as a `C`/`G`
+/// column, so the process table mirrors that.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessType {
+    Compute,
+    Graphics,
+}
+
+impl ProcessType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProcessType::Compute => "C",
+            ProcessType::Graphics => "G",
+        }
+    }
+}
+
+/// A process currently holding the GPU open (compute or graphics context).
+#[derive(Clone, Debug)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub used_memory: u64,
+    pub proc_type: ProcessType,
+}
+
+/// Which column a process panel is currently sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessSorting {
+    Pid,
+    Name,
+    Memory,
+}
+
+impl ProcessSorting {
+    /// Sorts `processes` by this column, honoring `reverse`. Shared by
+    /// `App::sorted_processes` (which picks the kill target) and
+    /// `ui::render_process_table` (which picks the highlighted row), so the
+    /// two can never disagree on row order.
+    pub fn sort(self, processes: &mut [&ProcessInfo], reverse: bool) {
+        match self {
+            ProcessSorting::Pid => processes.sort_by_key(|p| p.pid),
+            ProcessSorting::Name => processes.sort_by(|a, b| a.name.cmp(&b.name)),
+            ProcessSorting::Memory => processes.sort_by_key(|p| p.used_memory),
+        }
+        if reverse {
+            processes.reverse();
+        }
+    }
+}
+
+/// Which metrics a GPU/backend actually reports, so the UI can skip or grey
+/// out gauges instead of rendering misleading zeros.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SupportedMetrics {
+    pub utilization: bool,
+    pub memory: bool,
+    pub temperature: bool,
+    pub power: bool,
+    pub fan: bool,
+    pub clocks: bool,
+    pub pcie: bool,
+}
+
+/// GPU hardware vendor, tagging which collector a device came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Vendor {
+    Nvidia,
+    Amd,
+    Intel,
+}
+
+impl Vendor {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Vendor::Nvidia => "NVIDIA",
+            Vendor::Amd => "AMD",
+            Vendor::Intel => "Intel",
+        }
+    }
+
+    /// Inverse of [`Vendor::label`], for reading the vendor tag back out of
+    /// the history database during replay.
+    pub fn from_label(label: &str) -> Option<Vendor> {
+        match label {
+            "NVIDIA" => Some(Vendor::Nvidia),
+            "AMD" => Some(Vendor::Amd),
+            "Intel" => Some(Vendor::Intel),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct GpuInfo {
     pub name: String,
+    pub uuid: String,
+    pub vendor: Vendor,
     pub data_points: VecDeque<GpuDataPoint>,
+    pub processes: Vec<ProcessInfo>,
+    pub supported: SupportedMetrics,
+    /// Max SM/graphics clock in MHz, when the device/backend reports it.
+    /// Static for the life of the device, so it lives here rather than on
+    /// every `GpuDataPoint`.
+    pub max_sm_clock: Option<f64>,
+    /// Max memory clock in MHz, when the device/backend reports it.
+    pub max_mem_clock: Option<f64>,
 }
 
 impl GpuInfo {
-    pub fn new(name: String) -> Self {
+    pub fn new(name: String, vendor: Vendor) -> Self {
         Self {
             name,
+            uuid: String::new(),
+            vendor,
             data_points: VecDeque::new(),
+            processes: Vec::new(),
+            supported: SupportedMetrics::default(),
+            max_sm_clock: None,
+            max_mem_clock: None,
         }
     }
 }
@@ -1,13 +1,28 @@
-use chrono::{TimeDelta, Utc};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::fs;
 use std::process::Command;
 
-use crate::models::{GpuDataPoint, GpuInfo};
+use nvml_wrapper::enum_wrappers::device::{Clock, PcieUtilCounter, TemperatureSensor};
+use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::Nvml;
+
+use crate::models::{GpuDataPoint, GpuInfo, ProcessInfo, ProcessType, SupportedMetrics, Vendor};
+use crate::vendor::{trim_history, GpuBackend};
+
+/// Fallback backend: shells out to `nvidia-smi` and parses CSV.
+pub struct NvidiaSmiBackend;
+
+impl GpuBackend for NvidiaSmiBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
+        fetch_gpu_data(gpus)
+    }
+}
 
 /// Fetches GPU data from nvidia-smi and updates the provided GPU list.
-/// Returns the number of data points kept (last 60 minutes).
 pub fn fetch_gpu_data(gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
     let output = Command::new("nvidia-smi")
-        .arg("--query-gpu=index,name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw")
+        .arg("--query-gpu=index,name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw,uuid")
         .arg("--format=csv,noheader,nounits")
         .output()?;
 
@@ -16,43 +31,233 @@ pub fn fetch_gpu_data(gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error:
 
     for (idx, line) in output_str.lines().enumerate() {
         let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
-        if parts.len() >= 7 {
+        if parts.len() >= 8 {
             let gpu_index: usize = parts[0].parse().unwrap_or(idx);
             let name = parts[1].to_string();
-            let gpu_util: f64 = parts[2].parse().unwrap_or(0.0);
-            let memory_used: f64 = parts[3].parse().unwrap_or(0.0);
-            let memory_total: f64 = parts[4].parse().unwrap_or(0.0);
-            let temperature: f64 = parts[5].parse().unwrap_or(0.0);
-            let power_usage: f64 = parts[6].parse().unwrap_or(0.0);
+            // nvidia-smi prints "[N/A]" for metrics a device/driver doesn't report.
+            let gpu_util_raw = parts[2].parse::<f64>().ok();
+            let memory_used_raw = parts[3].parse::<f64>().ok();
+            let memory_total_raw = parts[4].parse::<f64>().ok();
+            let temperature_raw = parts[5].parse::<f64>().ok();
+            let power_raw = parts[6].parse::<f64>().ok();
+            let uuid = parts[7].to_string();
+
+            let supported = SupportedMetrics {
+                utilization: gpu_util_raw.is_some(),
+                memory: memory_used_raw.is_some() && memory_total_raw.is_some(),
+                temperature: temperature_raw.is_some(),
+                power: power_raw.is_some(),
+                fan: false,
+                clocks: false,
+                pcie: false,
+            };
 
             let data_point = GpuDataPoint {
                 timestamp: now,
-                gpu_util,
-                memory_used,
-                memory_total,
-                temperature,
-                power_usage,
+                gpu_util: gpu_util_raw.unwrap_or(0.0),
+                memory_used: memory_used_raw.unwrap_or(0.0),
+                memory_total: memory_total_raw.unwrap_or(0.0),
+                temperature: temperature_raw.unwrap_or(0.0),
+                power_usage: power_raw.unwrap_or(0.0),
+                fan_speed: None,
+                sm_clock: None,
+                mem_clock: None,
+                pcie_tx: None,
+                pcie_rx: None,
             };
 
             // Ensure GPU vector has enough elements
             while gpus.len() <= gpu_index {
-                gpus.push(GpuInfo::new(format!("GPU {}", gpus.len())));
+                gpus.push(GpuInfo::new(format!("GPU {}", gpus.len()), Vendor::Nvidia));
             }
 
             gpus[gpu_index].name = name;
+            gpus[gpu_index].uuid = uuid;
+            gpus[gpu_index].supported = supported;
             gpus[gpu_index].data_points.push_back(data_point);
+        }
+    }
+
+    trim_history(gpus, now);
+    fetch_processes_smi(gpus)?;
+
+    Ok(())
+}
+
+/// Fetches per-GPU compute processes via `nvidia-smi --query-compute-apps` and
+/// assigns each process to its owning GPU by matching on UUID. This endpoint
+/// only covers compute contexts, so every process it returns is tagged `C`;
+/// the NVML backend additionally queries graphics contexts for a `G` column.
+fn fetch_processes_smi(gpus: &mut [GpuInfo]) -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::new("nvidia-smi")
+        .arg("--query-compute-apps=pid,process_name,used_memory,gpu_uuid")
+        .arg("--format=csv,noheader,nounits")
+        .output()?;
+
+    let output_str = String::from_utf8(output.stdout)?;
+    let mut by_uuid: HashMap<String, Vec<ProcessInfo>> = HashMap::new();
+
+    for line in output_str.lines() {
+        let parts: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+        if parts.len() >= 4 {
+            let pid: u32 = match parts[0].parse() {
+                Ok(pid) => pid,
+                Err(_) => continue,
+            };
+            let name = parts[1].to_string();
+            let used_memory: u64 = parts[2].parse().unwrap_or(0);
+            let uuid = parts[3].to_string();
 
-            // Keep last 60 minutes of data
-            let cutoff = now - TimeDelta::try_minutes(60).unwrap_or_default();
-            while let Some(front) = gpus[gpu_index].data_points.front() {
-                if front.timestamp < cutoff {
-                    gpus[gpu_index].data_points.pop_front();
-                } else {
-                    break;
+            by_uuid.entry(uuid).or_default().push(ProcessInfo {
+                pid,
+                name,
+                used_memory,
+                proc_type: ProcessType::Compute,
+            });
+        }
+    }
+
+    for gpu in gpus.iter_mut() {
+        gpu.processes = by_uuid.remove(&gpu.uuid).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+/// Best-effort process name lookup for PIDs NVML only gives us the ID for.
+fn process_name(pid: u32) -> String {
+    fs::read_to_string(format!("/proc/{pid}/comm"))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|_| format!("pid {pid}"))
+}
+
+/// Native backend: talks to the driver directly through NVML, giving richer,
+/// lower-latency data than spawning `nvidia-smi` once per second.
+pub struct NvmlBackend {
+    nvml: Nvml,
+}
+
+impl NvmlBackend {
+    pub fn new() -> Result<Self, NvmlError> {
+        let nvml = Nvml::init()?;
+        Ok(Self { nvml })
+    }
+}
+
+impl GpuBackend for NvmlBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now();
+        let device_count = self.nvml.device_count()?;
+
+        for gpu_index in 0..device_count as usize {
+            let device = self.nvml.device_by_index(gpu_index as u32)?;
+
+            let name = device.name()?;
+            let uuid = device.uuid()?;
+            // Use `.ok()` rather than `?` for feature queries: a device lacking
+            // one sensor (e.g. no fan) shouldn't abort polling the rest.
+            let util = device.utilization_rates().ok();
+            let memory = device.memory_info().ok();
+            let temperature = device.temperature(TemperatureSensor::Gpu).ok();
+            let power_usage = device.power_usage().ok();
+            let fan_speed = device.fan_speed(0).ok().map(|pct| pct as f64);
+            let sm_clock = device
+                .clock_info(Clock::Graphics)
+                .ok()
+                .map(|mhz| mhz as f64);
+            let mem_clock = device.clock_info(Clock::Memory).ok().map(|mhz| mhz as f64);
+            let max_sm_clock = device
+                .max_clock_info(Clock::Graphics)
+                .ok()
+                .map(|mhz| mhz as f64);
+            let max_mem_clock = device
+                .max_clock_info(Clock::Memory)
+                .ok()
+                .map(|mhz| mhz as f64);
+            let pcie_tx = device
+                .pcie_throughput(PcieUtilCounter::Send)
+                .ok()
+                .map(|kbps| kbps as f64);
+            let pcie_rx = device
+                .pcie_throughput(PcieUtilCounter::Receive)
+                .ok()
+                .map(|kbps| kbps as f64);
+
+            let supported = SupportedMetrics {
+                utilization: util.is_some(),
+                memory: memory.is_some(),
+                temperature: temperature.is_some(),
+                power: power_usage.is_some(),
+                fan: fan_speed.is_some(),
+                clocks: sm_clock.is_some() || mem_clock.is_some(),
+                pcie: pcie_tx.is_some() || pcie_rx.is_some(),
+            };
+
+            let to_process_info = |proc: nvml_wrapper::struct_wrappers::device::ProcessInfo, proc_type: ProcessType| {
+                let used_memory = match proc.used_gpu_memory {
+                    nvml_wrapper::enums::device::UsedGpuMemory::Used(bytes) => bytes / (1024 * 1024),
+                    nvml_wrapper::enums::device::UsedGpuMemory::Unavailable => 0,
+                };
+                ProcessInfo {
+                    pid: proc.pid,
+                    name: process_name(proc.pid),
+                    used_memory,
+                    proc_type,
                 }
+            };
+
+            let mut processes: Vec<ProcessInfo> = device
+                .running_compute_processes()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|proc| to_process_info(proc, ProcessType::Compute))
+                .collect();
+            processes.extend(
+                device
+                    .running_graphics_processes()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|proc| to_process_info(proc, ProcessType::Graphics)),
+            );
+
+            let data_point = GpuDataPoint {
+                timestamp: now,
+                gpu_util: util.map(|u| u.gpu as f64).unwrap_or(0.0),
+                memory_used: memory.as_ref().map(|m| (m.used / 1_000_000) as f64).unwrap_or(0.0),
+                memory_total: memory.map(|m| (m.total / 1_000_000) as f64).unwrap_or(0.0),
+                temperature: temperature.map(|t| t as f64).unwrap_or(0.0),
+                power_usage: power_usage.map(|p| p as f64 / 1000.0).unwrap_or(0.0),
+                fan_speed,
+                sm_clock,
+                mem_clock,
+                pcie_tx,
+                pcie_rx,
+            };
+
+            while gpus.len() <= gpu_index {
+                gpus.push(GpuInfo::new(format!("GPU {}", gpus.len()), Vendor::Nvidia));
             }
+
+            gpus[gpu_index].name = name;
+            gpus[gpu_index].uuid = uuid;
+            gpus[gpu_index].supported = supported;
+            gpus[gpu_index].max_sm_clock = max_sm_clock;
+            gpus[gpu_index].max_mem_clock = max_mem_clock;
+            gpus[gpu_index].data_points.push_back(data_point);
+            gpus[gpu_index].processes = processes;
         }
+
+        trim_history(gpus, now);
+
+        Ok(())
     }
+}
 
-    Ok(())
+/// Selects NVML when the library loads successfully, falling back to the
+/// nvidia-smi subprocess otherwise (e.g. older drivers, missing libnvidia-ml).
+pub fn build_backend() -> Box<dyn GpuBackend> {
+    match NvmlBackend::new() {
+        Ok(backend) => Box::new(backend),
+        Err(_) => Box::new(NvidiaSmiBackend),
+    }
 }
@@ -0,0 +1,81 @@
+use crate::models::{GpuDataPoint, GpuInfo, SupportedMetrics, Vendor};
+use crate::storage::Store;
+use crate::vendor::GpuBackend;
+
+/// One recorded GPU's samples and how far playback has advanced into them.
+struct Track {
+    name: String,
+    vendor: Vendor,
+    samples: Vec<GpuDataPoint>,
+    cursor: usize,
+}
+
+/// Feeds `render` from a previously recorded history database instead of
+/// polling live hardware, so a past session can be reviewed with the normal
+/// UI. Selected via `--replay <file>`.
+///
+/// Playback advances one recorded sample per `poll()` call, the same cadence
+/// `App` uses for live polling, so freezing (`f`) pauses it and unfreezing
+/// steps it forward again — there's no separate seek control, freeze already
+/// is one.
+pub struct ReplayBackend {
+    tracks: Vec<Track>,
+}
+
+impl ReplayBackend {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let store = Store::open(path)?;
+        let mut tracks = Vec::new();
+
+        for gpu_index in store.distinct_gpu_indices()? {
+            let samples = store.load_all_history(gpu_index)?;
+            if samples.is_empty() {
+                continue;
+            }
+            let vendor = store.latest_vendor(gpu_index)?.unwrap_or(Vendor::Nvidia);
+            tracks.push(Track {
+                name: format!("Replay GPU {gpu_index}"),
+                vendor,
+                samples,
+                cursor: 0,
+            });
+        }
+
+        Ok(Self { tracks })
+    }
+}
+
+impl GpuBackend for ReplayBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
+        gpus.clear();
+
+        for track in &mut self.tracks {
+            let mut gpu = GpuInfo::new(track.name.clone(), track.vendor);
+            let end = (track.cursor + 1).min(track.samples.len());
+            gpu.data_points = track.samples[..end].iter().cloned().collect();
+
+            // The schema predates per-sample capability tracking, so infer it
+            // from which optional columns this recording actually populated
+            // rather than replaying misleading zeros as "reported".
+            if let Some(latest) = gpu.data_points.back() {
+                gpu.supported = SupportedMetrics {
+                    utilization: true,
+                    memory: true,
+                    temperature: true,
+                    power: true,
+                    fan: latest.fan_speed.is_some(),
+                    clocks: latest.sm_clock.is_some() || latest.mem_clock.is_some(),
+                    pcie: latest.pcie_tx.is_some() || latest.pcie_rx.is_some(),
+                };
+            }
+
+            gpus.push(gpu);
+
+            if track.cursor + 1 < track.samples.len() {
+                track.cursor += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use rusqlite::{params, Connection};
+
+use crate::models::{GpuDataPoint, Vendor};
+
+/// SQLite-backed history store, modeled as a thin wrapper over a single
+/// `samples` table so GPU data survives a restart.
+pub struct Store {
+    conn: Connection,
+}
+
+/// Adds any `samples` columns introduced since a given DB was first created.
+/// `CREATE TABLE IF NOT EXISTS` is a no-op against an existing table, so an
+/// older (e.g. pre-fan/clock/PCIe/vendor) on-disk schema would otherwise make
+/// every insert/load below silently fail against a genuinely missing column.
+fn migrate_samples_table(conn: &Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(samples)")?;
+    let existing: std::collections::HashSet<String> = stmt
+        .query_map(params![], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<_>>()?;
+    drop(stmt);
+
+    for (column, ddl_type) in [
+        ("fan_speed", "REAL"),
+        ("sm_clock", "REAL"),
+        ("mem_clock", "REAL"),
+        ("pcie_tx", "REAL"),
+        ("pcie_rx", "REAL"),
+        ("vendor", "TEXT"),
+    ] {
+        if !existing.contains(column) {
+            conn.execute_batch(&format!("ALTER TABLE samples ADD COLUMN {column} {ddl_type}"))?;
+        }
+    }
+    Ok(())
+}
+
+impl Store {
+    /// Opens (creating if needed) the SQLite file at `path` and ensures the
+    /// schema exists, migrating an older on-disk `samples` table forward if
+    /// needed (e.g. one predating the fan/clock/PCIe/vendor columns).
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS samples (
+                gpu_index  INTEGER NOT NULL,
+                timestamp  TEXT NOT NULL,
+                util       REAL NOT NULL,
+                mem_used   REAL NOT NULL,
+                mem_total  REAL NOT NULL,
+                temperature REAL NOT NULL,
+                power      REAL NOT NULL,
+                fan_speed  REAL,
+                sm_clock   REAL,
+                mem_clock  REAL,
+                pcie_tx    REAL,
+                pcie_rx    REAL,
+                vendor     TEXT
+             );
+             CREATE INDEX IF NOT EXISTS idx_samples_gpu_ts ON samples (gpu_index, timestamp);",
+        )?;
+        migrate_samples_table(&conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Appends one sample for `gpu_index`, tagged with the vendor it came
+    /// from so a replay session can reconstruct the same `GpuInfo.vendor`.
+    pub fn insert(&self, gpu_index: usize, point: &GpuDataPoint, vendor: Vendor) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO samples (gpu_index, timestamp, util, mem_used, mem_total, temperature, power,
+                                   fan_speed, sm_clock, mem_clock, pcie_tx, pcie_rx, vendor)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                gpu_index as i64,
+                point.timestamp.to_rfc3339(),
+                point.gpu_util,
+                point.memory_used,
+                point.memory_total,
+                point.temperature,
+                point.power_usage,
+                point.fan_speed,
+                point.sm_clock,
+                point.mem_clock,
+                point.pcie_tx,
+                point.pcie_rx,
+                vendor.label(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Loads samples for `gpu_index` within `window` of the present, oldest first.
+    pub fn load_history(&self, gpu_index: usize, window: TimeDelta) -> rusqlite::Result<Vec<GpuDataPoint>> {
+        let cutoff = Utc::now() - window;
+        self.query_samples(
+            "SELECT timestamp, util, mem_used, mem_total, temperature, power, fan_speed, sm_clock, mem_clock, pcie_tx, pcie_rx
+             FROM samples WHERE gpu_index = ?1 AND timestamp >= ?2 ORDER BY timestamp ASC",
+            params![gpu_index as i64, cutoff.to_rfc3339()],
+        )
+    }
+
+    /// Loads every stored sample for `gpu_index`, oldest first, with no time
+    /// window — used to seed a replay session from the full recording.
+    pub fn load_all_history(&self, gpu_index: usize) -> rusqlite::Result<Vec<GpuDataPoint>> {
+        self.query_samples(
+            "SELECT timestamp, util, mem_used, mem_total, temperature, power, fan_speed, sm_clock, mem_clock, pcie_tx, pcie_rx
+             FROM samples WHERE gpu_index = ?1 ORDER BY timestamp ASC",
+            params![gpu_index as i64],
+        )
+    }
+
+    fn query_samples(&self, sql: &str, params: &[&dyn rusqlite::ToSql]) -> rusqlite::Result<Vec<GpuDataPoint>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            let timestamp: String = row.get(0)?;
+            let timestamp = DateTime::parse_from_rfc3339(&timestamp)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+            Ok(GpuDataPoint {
+                timestamp,
+                gpu_util: row.get(1)?,
+                memory_used: row.get(2)?,
+                memory_total: row.get(3)?,
+                temperature: row.get(4)?,
+                power_usage: row.get(5)?,
+                fan_speed: row.get(6)?,
+                sm_clock: row.get(7)?,
+                mem_clock: row.get(8)?,
+                pcie_tx: row.get(9)?,
+                pcie_rx: row.get(10)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Deletes every sample older than `cutoff`, returning the row count
+    /// removed. Keeps `gpu_history.db` from growing without bound once the
+    /// history window is no longer expanding.
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> rusqlite::Result<usize> {
+        self.conn
+            .execute("DELETE FROM samples WHERE timestamp < ?1", params![cutoff.to_rfc3339()])
+    }
+
+    /// GPU indices that have at least one recorded sample, ascending — used
+    /// to discover how many tracks a replay session has to play back.
+    pub fn distinct_gpu_indices(&self) -> rusqlite::Result<Vec<usize>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT gpu_index FROM samples ORDER BY gpu_index ASC")?;
+        let rows = stmt.query_map(params![], |row| row.get::<_, i64>(0))?;
+        rows.map(|r| r.map(|v| v as usize)).collect()
+    }
+
+    /// The vendor tag on `gpu_index`'s most recent sample, if any was recorded.
+    pub fn latest_vendor(&self, gpu_index: usize) -> rusqlite::Result<Option<Vendor>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT vendor FROM samples WHERE gpu_index = ?1 ORDER BY timestamp DESC LIMIT 1")?;
+        let label: Option<Option<String>> = stmt
+            .query_map(params![gpu_index as i64], |row| row.get::<_, Option<String>>(0))?
+            .next()
+            .transpose()?;
+        Ok(label.flatten().and_then(|l| Vendor::from_label(&l)))
+    }
+}
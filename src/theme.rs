@@ -1,11 +1,177 @@
+use std::path::Path;
+
 use ratatui::style::Color;
+use serde::Deserialize;
+
+/// Named color palette for the whole UI. Replaces the old module-level
+/// `const Color` values so the palette can be swapped at runtime via
+/// `--theme` instead of being baked into the binary.
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    pub neon_green: Color,
+    pub neon_cyan: Color,
+    pub neon_magenta: Color,
+    pub neon_yellow: Color,
+    pub neon_red: Color,
+    pub dark_bg: Color,
+    pub matrix_green: Color,
+    pub cyber_blue: Color,
+}
+
+/// On-disk shape for a TOML/JSON theme file: plain `"#rrggbb"` strings, since
+/// `ratatui::style::Color` has no `Deserialize` impl to derive against.
+#[derive(Deserialize)]
+struct ThemeFile {
+    neon_green: String,
+    neon_cyan: String,
+    neon_magenta: String,
+    neon_yellow: String,
+    neon_red: String,
+    dark_bg: String,
+    matrix_green: String,
+    cyber_blue: String,
+}
+
+impl Theme {
+    /// The muted "hacker" palette this app has always shipped with.
+    pub const HACKER: Theme = Theme {
+        neon_green: Color::Rgb(0, 160, 50),
+        neon_cyan: Color::Rgb(0, 150, 160),
+        neon_magenta: Color::Rgb(160, 60, 160),
+        neon_yellow: Color::Rgb(180, 160, 60),
+        neon_red: Color::Rgb(180, 60, 60),
+        dark_bg: Color::Rgb(15, 15, 25),
+        matrix_green: Color::Rgb(30, 130, 30),
+        cyber_blue: Color::Rgb(60, 130, 180),
+    };
+
+    /// Brighter, near-black-background palette for low-vision/high-contrast setups.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        neon_green: Color::Rgb(0, 255, 90),
+        neon_cyan: Color::Rgb(0, 230, 255),
+        neon_magenta: Color::Rgb(255, 80, 255),
+        neon_yellow: Color::Rgb(255, 220, 0),
+        neon_red: Color::Rgb(255, 60, 60),
+        dark_bg: Color::Rgb(0, 0, 0),
+        matrix_green: Color::Rgb(0, 200, 90),
+        cyber_blue: Color::Rgb(90, 170, 255),
+    };
+
+    /// Okabe-Ito inspired palette that avoids relying on red/green alone to
+    /// carry meaning, for deuteranopia/protanopia color vision.
+    pub const COLORBLIND: Theme = Theme {
+        neon_green: Color::Rgb(0, 114, 178),
+        neon_cyan: Color::Rgb(86, 180, 233),
+        neon_magenta: Color::Rgb(204, 121, 167),
+        neon_yellow: Color::Rgb(230, 159, 0),
+        neon_red: Color::Rgb(213, 94, 0),
+        dark_bg: Color::Rgb(15, 15, 25),
+        matrix_green: Color::Rgb(0, 90, 140),
+        cyber_blue: Color::Rgb(0, 158, 115),
+    };
+
+    /// Looks up a built-in preset by name (case-insensitive).
+    pub fn preset(name: &str) -> Option<Theme> {
+        match name.to_ascii_lowercase().as_str() {
+            "hacker" | "default" => Some(Theme::HACKER),
+            "high-contrast" | "high_contrast" => Some(Theme::HIGH_CONTRAST),
+            "colorblind" => Some(Theme::COLORBLIND),
+            _ => None,
+        }
+    }
+
+    /// Resolves a `--theme <name|path>` argument: a built-in preset name, or
+    /// a path to a TOML/JSON file with the same fields as a preset.
+    pub fn load(name_or_path: &str) -> Result<Theme, Box<dyn std::error::Error>> {
+        if let Some(preset) = Theme::preset(name_or_path) {
+            return Ok(preset);
+        }
+
+        let path = Path::new(name_or_path);
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("unknown theme {name_or_path:?} ({e})"))?;
+        let file: ThemeFile = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            serde_json::from_str(&contents)?
+        } else {
+            toml::from_str(&contents)?
+        };
+
+        Ok(Theme {
+            neon_green: parse_hex(&file.neon_green)?,
+            neon_cyan: parse_hex(&file.neon_cyan)?,
+            neon_magenta: parse_hex(&file.neon_magenta)?,
+            neon_yellow: parse_hex(&file.neon_yellow)?,
+            neon_red: parse_hex(&file.neon_red)?,
+            dark_bg: parse_hex(&file.dark_bg)?,
+            matrix_green: parse_hex(&file.matrix_green)?,
+            cyber_blue: parse_hex(&file.cyber_blue)?,
+        })
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::HACKER
+    }
+}
+
+impl Theme {
+    /// Maps `value` to a smoothly-interpolated color instead of snapping
+    /// between hardcoded thresholds: green at zero, yellow at `low`, red at
+    /// and beyond `high`, blending through RGB space in between. Lets
+    /// every meter (util, temp, ...) share one tuning point instead of each
+    /// hand-rolling its own `if value > X` ladder.
+    pub fn severity_color(&self, value: f64, low: f64, high: f64) -> Color {
+        let t = if value <= low {
+            if low > 0.0 {
+                0.5 * (value / low).clamp(0.0, 1.0)
+            } else {
+                0.5
+            }
+        } else {
+            let span = (high - low).max(f64::EPSILON);
+            0.5 + 0.5 * ((value - low) / span).clamp(0.0, 1.0)
+        };
+
+        if t <= 0.5 {
+            lerp_color(self.neon_green, self.neon_yellow, t / 0.5)
+        } else {
+            lerp_color(self.neon_yellow, self.neon_red, (t - 0.5) / 0.5)
+        }
+    }
+}
+
+/// Linearly blends two `Color::Rgb` values channel-by-channel; non-RGB
+/// variants (none are used by `Theme`) fall back to black.
+fn lerp_color(from: Color, to: Color, t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let (fr, fg, fb) = rgb_components(from);
+    let (tr, tg, tb) = rgb_components(to);
+    Color::Rgb(
+        lerp_channel(fr, tr, t),
+        lerp_channel(fg, tg, t),
+        lerp_channel(fb, tb, t),
+    )
+}
+
+fn rgb_components(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        _ => (0, 0, 0),
+    }
+}
+
+fn lerp_channel(from: u8, to: u8, t: f64) -> u8 {
+    (from as f64 + (to as f64 - from as f64) * t).round().clamp(0.0, 255.0) as u8
+}
 
-// Приглушённая хакерская цветовая схема
-pub const NEON_GREEN: Color = Color::Rgb(0, 160, 50);
-pub const NEON_CYAN: Color = Color::Rgb(0, 150, 160);
-pub const NEON_MAGENTA: Color = Color::Rgb(160, 60, 160);
-pub const NEON_YELLOW: Color = Color::Rgb(180, 160, 60);
-pub const NEON_RED: Color = Color::Rgb(180, 60, 60);
-pub const DARK_BG: Color = Color::Rgb(15, 15, 25);
-pub const MATRIX_GREEN: Color = Color::Rgb(30, 130, 30);
-pub const CYBER_BLUE: Color = Color::Rgb(60, 130, 180);
+fn parse_hex(value: &str) -> Result<Color, Box<dyn std::error::Error>> {
+    let hex = value.trim_start_matches('#');
+    if hex.len() != 6 {
+        return Err(format!("invalid color {value:?}, expected #rrggbb").into());
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16)?;
+    let g = u8::from_str_radix(&hex[2..4], 16)?;
+    let b = u8::from_str_radix(&hex[4..6], 16)?;
+    Ok(Color::Rgb(r, g, b))
+}
@@ -2,18 +2,59 @@ use chrono::{TimeDelta, Utc};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols::Marker,
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Gauge, Paragraph, Sparkline},
+    widgets::{
+        Axis, Block, BorderType, Borders, Cell, Chart, Clear, Dataset, GraphType, Gauge,
+        Paragraph, Row, Table, TableState,
+    },
     Frame,
 };
 
-use crate::models::{GpuDataPoint, GpuInfo};
-use crate::theme::*;
+use crate::app::TimingStats;
+use crate::models::{GpuDataPoint, GpuInfo, ProcessInfo, ProcessSorting, SupportedMetrics};
+use crate::theme::Theme;
+
+/// View state for the process panel and kill dialog, owned by `App` and
+/// threaded through read-only so `ui` never has to reach back into `app`.
+pub struct ProcessView {
+    pub selected_gpu: usize,
+    pub sort: ProcessSorting,
+    pub sort_reverse: bool,
+    pub selected_process: usize,
+    pub pending_kill: Option<u32>,
+}
+
+/// Snapshot of the `App`'s rolling frame/poll timing stats, passed in only
+/// when the profiler overlay (`t`) is toggled on.
+pub struct ProfilerView {
+    pub frame: TimingStats,
+    pub fps: f64,
+    pub poll: Option<TimingStats>,
+}
+
+/// Rendering-wide config that doesn't belong to any single panel: the active
+/// palette and the optional profiler snapshot. Bundled so `render` doesn't
+/// grow one positional argument per cross-cutting feature.
+pub struct RenderContext<'a> {
+    pub theme: &'a Theme,
+    pub profiler: Option<&'a ProfilerView>,
+}
 
 /// Renders the main UI with header and GPU panels
-pub fn render(frame: &mut Frame, gpus: &[GpuInfo], frame_count: u64) {
+pub fn render(
+    frame: &mut Frame,
+    gpus: &[GpuInfo],
+    frame_count: u64,
+    process_view: &ProcessView,
+    is_frozen: bool,
+    show_help: bool,
+    ctx: &RenderContext,
+) {
+    let theme = ctx.theme;
+
     // Main container with dark background
-    let main_block = Block::default().style(Style::default().bg(DARK_BG));
+    let main_block = Block::default().style(Style::default().bg(theme.dark_bg));
     frame.render_widget(main_block, frame.area());
 
     let chunks = Layout::default()
@@ -25,30 +66,165 @@ pub fn render(frame: &mut Frame, gpus: &[GpuInfo], frame_count: u64) {
         ])
         .split(frame.area());
 
-    render_header(frame, chunks[0], gpus, frame_count);
+    render_header(frame, chunks[0], gpus, frame_count, is_frozen, theme);
+
+    if let Some(profiler) = ctx.profiler {
+        render_profiler_overlay(frame, chunks[0], profiler, theme);
+    }
 
     if gpus.is_empty() {
-        render_no_gpu(frame, chunks[1], frame_count);
+        render_no_gpu(frame, chunks[1], frame_count, theme);
         return;
     }
 
-    // Dynamic layout for GPUs
+    // The focused GPU's panel expands; the rest collapse to a single summary line.
     let gpu_count = gpus.len();
+    let focus = process_view.selected_gpu.min(gpu_count - 1);
+    let constraints: Vec<Constraint> = if gpu_count > 1 {
+        (0..gpu_count)
+            .map(|idx| {
+                if idx == focus {
+                    Constraint::Min(10)
+                } else {
+                    Constraint::Length(3)
+                }
+            })
+            .collect()
+    } else {
+        vec![Constraint::Min(0)]
+    };
     let gpu_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(
-            (0..gpu_count)
-                .map(|_| Constraint::Ratio(1, gpu_count as u32))
-                .collect::<Vec<_>>(),
-        )
+        .constraints(constraints)
         .split(chunks[1]);
 
     for (gpu_idx, gpu) in gpus.iter().enumerate() {
-        render_gpu(frame, gpu_idx, gpu, gpu_chunks[gpu_idx]);
+        if gpu_count > 1 && gpu_idx != focus {
+            render_gpu_collapsed(frame, gpu_idx, gpu, gpu_chunks[gpu_idx], theme);
+        } else {
+            render_gpu(
+                frame,
+                gpu_idx,
+                gpu,
+                gpu_chunks[gpu_idx],
+                process_view,
+                gpu_idx == focus,
+                theme,
+            );
+        }
+    }
+
+    if let Some(pid) = process_view.pending_kill {
+        render_kill_dialog(frame, frame.area(), pid, theme);
+    }
+
+    if show_help {
+        render_help_overlay(frame, frame.area(), theme);
+    }
+}
+
+/// Single-line summary for a collapsed (unfocused) GPU panel.
+fn render_gpu_collapsed(frame: &mut Frame, gpu_idx: usize, gpu: &GpuInfo, area: Rect, theme: &Theme) {
+    let Some(latest) = gpu.data_points.back() else {
+        return;
+    };
+
+    let supported = gpu.supported;
+    let status_color = if !supported.utilization {
+        Color::DarkGray
+    } else {
+        theme.severity_color(latest.gpu_util, 50.0, 90.0)
+    };
+
+    let mut spans = vec![
+        Span::styled(" ◆ ", Style::default().fg(status_color)),
+        Span::styled(
+            format!("GPU {gpu_idx} "),
+            Style::default().fg(theme.neon_green).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("[{}] ", gpu.vendor.label()), Style::default().fg(Color::DarkGray)),
+        Span::styled(&gpu.name, Style::default().fg(theme.cyber_blue)),
+    ];
+    if supported.utilization {
+        spans.push(Span::styled("  UTIL: ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("{:5.1}%", latest.gpu_util),
+            Style::default().fg(status_color),
+        ));
     }
+    if supported.temperature {
+        spans.push(Span::styled("  TEMP: ", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!("{:3.0}°C", latest.temperature),
+            Style::default().fg(theme.neon_cyan),
+        ));
+    }
+    let line = Line::from(spans);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.matrix_green))
+        .style(Style::default().bg(theme.dark_bg));
+
+    frame.render_widget(Paragraph::new(line).block(block), area);
 }
 
-fn render_header(frame: &mut Frame, area: Rect, gpus: &[GpuInfo], frame_count: u64) {
+/// Centered overlay listing keybindings, toggled with `?`.
+fn render_help_overlay(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let width = 46.min(area.width);
+    let height = 14.min(area.height);
+    let dialog_area = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let bindings = [
+        ("q / Esc", "Quit"),
+        ("f", "Freeze/unfreeze updates"),
+        ("t", "Toggle frame-time profiler overlay"),
+        ("Tab / Shift+Tab", "Focus next/prev GPU"),
+        ("Ctrl-r", "Reset history"),
+        ("[ / ]", "Narrow/widen history window"),
+        ("p / n / m", "Sort processes by PID/name/memory"),
+        ("r", "Reverse process sort"),
+        ("Up / Down", "Select process"),
+        ("dd", "Kill selected process"),
+        ("?", "Toggle this help"),
+    ];
+
+    let mut lines = vec![Line::from(vec![Span::styled(
+        "KEYBINDINGS",
+        Style::default().fg(theme.neon_green).add_modifier(Modifier::BOLD),
+    )])];
+    for (keys, desc) in bindings {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{keys:<16}"), Style::default().fg(theme.neon_yellow)),
+            Span::styled(desc, Style::default().fg(Color::White)),
+        ]));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(theme.neon_green))
+        .style(Style::default().bg(theme.dark_bg))
+        .title(" HELP ");
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(Paragraph::new(lines).block(block), dialog_area);
+}
+
+fn render_header(
+    frame: &mut Frame,
+    area: Rect,
+    gpus: &[GpuInfo],
+    frame_count: u64,
+    is_frozen: bool,
+    theme: &Theme,
+) {
     let now = Utc::now();
     let uptime = gpus
         .first()
@@ -61,22 +237,22 @@ fn render_header(frame: &mut Frame, area: Rect, gpus: &[GpuInfo], frame_count: u
     let header_text = vec![
         Line::from(vec![Span::styled(
             "╔══════════════════════════════════════════════════════════════╗",
-            Style::default().fg(NEON_GREEN),
+            Style::default().fg(theme.neon_green),
         )]),
         Line::from(vec![
-            Span::styled("║  ", Style::default().fg(NEON_GREEN)),
-            Span::styled(glitch_char, Style::default().fg(NEON_MAGENTA)),
+            Span::styled("║  ", Style::default().fg(theme.neon_green)),
+            Span::styled(glitch_char, Style::default().fg(theme.neon_magenta)),
             Span::styled(
                 " GPU MONITOR ",
-                Style::default().fg(NEON_GREEN).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.neon_green).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("v1.0 ", Style::default().fg(CYBER_BLUE)),
-            Span::styled("│ ", Style::default().fg(NEON_GREEN)),
+            Span::styled("v1.0 ", Style::default().fg(theme.cyber_blue)),
+            Span::styled("│ ", Style::default().fg(theme.neon_green)),
             Span::styled(
                 format!("{}", now.format("%H:%M:%S")),
-                Style::default().fg(NEON_YELLOW),
+                Style::default().fg(theme.neon_yellow),
             ),
-            Span::styled(" │ ", Style::default().fg(NEON_GREEN)),
+            Span::styled(" │ ", Style::default().fg(theme.neon_green)),
             Span::styled(
                 format!(
                     "UPTIME: {:02}:{:02}:{:02}",
@@ -84,49 +260,84 @@ fn render_header(frame: &mut Frame, area: Rect, gpus: &[GpuInfo], frame_count: u
                     uptime.num_minutes() % 60,
                     uptime.num_seconds() % 60
                 ),
-                Style::default().fg(NEON_CYAN),
+                Style::default().fg(theme.neon_cyan),
             ),
-            Span::styled(" │ ", Style::default().fg(NEON_GREEN)),
+            Span::styled(" │ ", Style::default().fg(theme.neon_green)),
             Span::styled(
                 format!("GPUs: {}", gpus.len()),
-                Style::default().fg(NEON_MAGENTA),
+                Style::default().fg(theme.neon_magenta),
             ),
-            Span::styled(format!("{:>2}║", ""), Style::default().fg(NEON_GREEN)),
+            Span::styled(
+                if is_frozen { " │ FROZEN" } else { "" },
+                Style::default().fg(theme.neon_red).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{:>2}║", ""), Style::default().fg(theme.neon_green)),
         ]),
         Line::from(vec![Span::styled(
             "╚══════════════════════════════════════════════════════════════╝",
-            Style::default().fg(NEON_GREEN),
+            Style::default().fg(theme.neon_green),
         )]),
     ];
 
     let header = Paragraph::new(header_text)
-        .style(Style::default().bg(DARK_BG))
+        .style(Style::default().bg(theme.dark_bg))
         .alignment(Alignment::Left);
     frame.render_widget(header, area);
 }
 
-fn render_no_gpu(frame: &mut Frame, area: Rect, frame_count: u64) {
+/// Small frame-time/poll-time readout docked to the header's right edge,
+/// toggled with `t`. Diagnoses stutter by showing whether the render loop or
+/// the backend's data poll is the bottleneck.
+fn render_profiler_overlay(frame: &mut Frame, header_area: Rect, profiler: &ProfilerView, theme: &Theme) {
+    let width = 34.min(header_area.width);
+    let area = Rect {
+        x: header_area.right().saturating_sub(width + 1),
+        y: header_area.y + 1,
+        width,
+        height: 1,
+    };
+
+    let poll_text = profiler
+        .poll
+        .map(|p| format!(" poll {:.1}/{:.1}/{:.1}ms", p.min_ms, p.avg_ms, p.max_ms))
+        .unwrap_or_default();
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!(
+                "frame {:.1}/{:.1}/{:.1}ms {:.0}fps",
+                profiler.frame.min_ms, profiler.frame.avg_ms, profiler.frame.max_ms, profiler.fps
+            ),
+            Style::default().fg(theme.neon_cyan),
+        ),
+        Span::styled(poll_text, Style::default().fg(Color::DarkGray)),
+    ]);
+
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Right), area);
+}
+
+fn render_no_gpu(frame: &mut Frame, area: Rect, frame_count: u64, theme: &Theme) {
     let blink = if frame_count % 20 < 10 { "█" } else { " " };
     let text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("  [", Style::default().fg(NEON_RED)),
+            Span::styled("  [", Style::default().fg(theme.neon_red)),
             Span::styled(
                 "!",
                 Style::default()
-                    .fg(NEON_YELLOW)
+                    .fg(theme.neon_yellow)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("] ", Style::default().fg(NEON_RED)),
+            Span::styled("] ", Style::default().fg(theme.neon_red)),
             Span::styled(
                 "SCANNING FOR GPU DEVICES",
-                Style::default().fg(NEON_RED).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.neon_red).add_modifier(Modifier::BOLD),
             ),
-            Span::styled(blink, Style::default().fg(NEON_GREEN)),
+            Span::styled(blink, Style::default().fg(theme.neon_green)),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
-            "      Waiting for nvidia-smi response...",
+            "      Waiting for GPU backend response...",
             Style::default().fg(Color::DarkGray),
         )]),
     ];
@@ -134,14 +345,22 @@ fn render_no_gpu(frame: &mut Frame, area: Rect, frame_count: u64) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Double)
-        .border_style(Style::default().fg(NEON_RED))
-        .style(Style::default().bg(DARK_BG));
+        .border_style(Style::default().fg(theme.neon_red))
+        .style(Style::default().bg(theme.dark_bg));
 
     let paragraph = Paragraph::new(text).block(block);
     frame.render_widget(paragraph, area);
 }
 
-fn render_gpu(frame: &mut Frame, gpu_idx: usize, gpu: &GpuInfo, area: Rect) {
+fn render_gpu(
+    frame: &mut Frame,
+    gpu_idx: usize,
+    gpu: &GpuInfo,
+    area: Rect,
+    process_view: &ProcessView,
+    is_selected: bool,
+    theme: &Theme,
+) {
     let data: Vec<&GpuDataPoint> = gpu.data_points.iter().collect();
 
     if data.is_empty() {
@@ -149,159 +368,467 @@ fn render_gpu(frame: &mut Frame, gpu_idx: usize, gpu: &GpuInfo, area: Rect) {
     }
 
     let latest = data.last().unwrap();
+    let supported = gpu.supported;
 
-    // Status color based on load
-    let status_color = if latest.gpu_util > 90.0 {
-        NEON_RED
-    } else if latest.gpu_util > 50.0 {
-        NEON_YELLOW
+    // Status color based on load; greyed out when the metric isn't reported.
+    let status_color = if !supported.utilization {
+        Color::DarkGray
     } else {
-        NEON_GREEN
+        theme.severity_color(latest.gpu_util, 50.0, 90.0)
     };
 
-    let temp_color = if latest.temperature > 80.0 {
-        NEON_RED
-    } else if latest.temperature > 60.0 {
-        NEON_YELLOW
+    let temp_color = if !supported.temperature {
+        Color::DarkGray
     } else {
-        NEON_CYAN
+        theme.severity_color(latest.temperature, 60.0, 80.0)
     };
 
     // GPU block
     let gpu_block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(MATRIX_GREEN))
+        .border_style(Style::default().fg(theme.matrix_green))
         .title(vec![
             Span::styled(" ◆ ", Style::default().fg(status_color)),
             Span::styled(
                 format!("GPU {} ", gpu_idx),
-                Style::default().fg(NEON_GREEN).add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.neon_green).add_modifier(Modifier::BOLD),
             ),
-            Span::styled("│ ", Style::default().fg(MATRIX_GREEN)),
-            Span::styled(&gpu.name, Style::default().fg(CYBER_BLUE)),
+            Span::styled("│ ", Style::default().fg(theme.matrix_green)),
+            Span::styled(format!("[{}] ", gpu.vendor.label()), Style::default().fg(Color::DarkGray)),
+            Span::styled(&gpu.name, Style::default().fg(theme.cyber_blue)),
             Span::styled(" ", Style::default()),
         ])
-        .style(Style::default().bg(DARK_BG));
+        .style(Style::default().bg(theme.dark_bg));
 
     frame.render_widget(gpu_block, area);
 
+    // Reflow the layout to only reserve space for metrics this GPU actually
+    // reports, instead of a fixed set of rows with misleading zeros.
+    let show_chart = supported.utilization || supported.temperature || supported.power;
+    let show_processes = !gpu.processes.is_empty();
+
+    let mut constraints = vec![Constraint::Length(2)]; // Stats line (always shown)
+    if supported.utilization {
+        constraints.push(Constraint::Length(3));
+    }
+    if supported.memory {
+        constraints.push(Constraint::Length(3));
+    }
+    if supported.clocks {
+        constraints.push(Constraint::Length(3));
+    }
+    if supported.fan {
+        constraints.push(Constraint::Length(3));
+    }
+    if show_chart {
+        constraints.push(Constraint::Min(6));
+    }
+    if show_processes {
+        constraints.push(Constraint::Length(6));
+    }
+
     let inner = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .constraints([
-            Constraint::Length(2), // Stats line
-            Constraint::Length(3), // GPU Util bar
-            Constraint::Length(3), // Memory bar
-            Constraint::Min(3),    // Sparklines
-        ])
+        .constraints(constraints)
         .split(area);
+    let mut next = inner.iter();
 
-    // Stats line
-    let stats_line = Line::from(vec![
-        Span::styled("  ┌─ ", Style::default().fg(MATRIX_GREEN)),
-        Span::styled("UTIL: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
+    // Stats line: only the spans for metrics this GPU reports.
+    let mut stats_spans = vec![Span::styled("  ┌─ ", Style::default().fg(theme.matrix_green))];
+    if supported.utilization {
+        stats_spans.push(Span::styled("UTIL: ", Style::default().fg(Color::DarkGray)));
+        stats_spans.push(Span::styled(
             format!("{:5.1}%", latest.gpu_util),
-            Style::default()
-                .fg(status_color)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" │ ", Style::default().fg(MATRIX_GREEN)),
-        Span::styled("TEMP: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
+            Style::default().fg(status_color).add_modifier(Modifier::BOLD),
+        ));
+        stats_spans.push(Span::styled(" │ ", Style::default().fg(theme.matrix_green)));
+    }
+    if supported.temperature {
+        stats_spans.push(Span::styled("TEMP: ", Style::default().fg(Color::DarkGray)));
+        stats_spans.push(Span::styled(
             format!("{:3.0}°C", latest.temperature),
             Style::default().fg(temp_color).add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" │ ", Style::default().fg(MATRIX_GREEN)),
-        Span::styled("PWR: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
+        ));
+        stats_spans.push(Span::styled(" │ ", Style::default().fg(theme.matrix_green)));
+    }
+    if supported.power {
+        stats_spans.push(Span::styled("PWR: ", Style::default().fg(Color::DarkGray)));
+        stats_spans.push(Span::styled(
             format!("{:6.1}W", latest.power_usage),
-            Style::default().fg(NEON_YELLOW),
-        ),
-        Span::styled(" │ ", Style::default().fg(MATRIX_GREEN)),
-        Span::styled("MEM: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
+            Style::default().fg(theme.neon_yellow),
+        ));
+        stats_spans.push(Span::styled(" │ ", Style::default().fg(theme.matrix_green)));
+    }
+    if supported.memory {
+        stats_spans.push(Span::styled("MEM: ", Style::default().fg(Color::DarkGray)));
+        stats_spans.push(Span::styled(
             format!("{:.0}/{:.0}MB", latest.memory_used, latest.memory_total),
-            Style::default().fg(NEON_MAGENTA),
-        ),
-        Span::styled(" ─┐", Style::default().fg(MATRIX_GREEN)),
-    ]);
-    let stats = Paragraph::new(stats_line).style(Style::default().bg(DARK_BG));
-    frame.render_widget(stats, inner[0]);
-
-    // GPU Utilization bar
-    let util_label = format!("▓ GPU {:5.1}%", latest.gpu_util);
-    let util_gauge = Gauge::default()
-        .block(
-            Block::default()
-                .borders(Borders::NONE)
-                .style(Style::default().bg(DARK_BG)),
-        )
-        .gauge_style(Style::default().fg(status_color).bg(Color::Rgb(20, 20, 30)))
-        .percent(latest.gpu_util as u16)
-        .label(Span::styled(
-            util_label,
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+            Style::default().fg(theme.neon_magenta),
         ));
-    frame.render_widget(util_gauge, inner[1]);
-
-    // Memory bar
-    let mem_percent = (latest.memory_used / latest.memory_total) * 100.0;
-    let mem_label = format!("▓ MEM {:5.1}%", mem_percent);
-    let mem_gauge = Gauge::default()
-        .block(
-            Block::default()
-                .borders(Borders::NONE)
-                .style(Style::default().bg(DARK_BG)),
-        )
-        .gauge_style(Style::default().fg(NEON_MAGENTA).bg(Color::Rgb(20, 20, 30)))
-        .percent(mem_percent as u16)
-        .label(Span::styled(
-            mem_label,
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+    }
+    if supported.pcie {
+        stats_spans.push(Span::styled(" │ ", Style::default().fg(theme.matrix_green)));
+        stats_spans.push(Span::styled("PCIE: ", Style::default().fg(Color::DarkGray)));
+        stats_spans.push(Span::styled(
+            format!(
+                "↑{:.0} ↓{:.0} KB/s",
+                latest.pcie_tx.unwrap_or(0.0),
+                latest.pcie_rx.unwrap_or(0.0)
+            ),
+            Style::default().fg(theme.cyber_blue),
+        ));
+    }
+    if stats_spans.len() == 1 {
+        // Nothing this backend reports made it into the line above; say so
+        // instead of leaving a blank bar that looks like a stalled poll.
+        stats_spans.push(Span::styled(
+            "no metrics reported by this device/backend",
+            Style::default().fg(Color::DarkGray),
         ));
-    frame.render_widget(mem_gauge, inner[2]);
+    }
+    stats_spans.push(Span::styled(" ─┐", Style::default().fg(theme.matrix_green)));
+    let stats = Paragraph::new(Line::from(stats_spans)).style(Style::default().bg(theme.dark_bg));
+    frame.render_widget(stats, *next.next().unwrap());
 
-    // Sparklines row
-    let spark_chunks = Layout::default()
+    if supported.utilization {
+        let util_gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default().bg(theme.dark_bg)),
+            )
+            .gauge_style(Style::default().fg(status_color).bg(Color::Rgb(20, 20, 30)))
+            .percent(latest.gpu_util as u16)
+            .label(Span::styled(
+                format!("▓ GPU {:5.1}%", latest.gpu_util),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ));
+        frame.render_widget(util_gauge, *next.next().unwrap());
+    }
+
+    if supported.memory {
+        let mem_percent = if latest.memory_total > 0.0 {
+            ((latest.memory_used / latest.memory_total) * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        };
+        let mem_gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default().bg(theme.dark_bg)),
+            )
+            .gauge_style(Style::default().fg(theme.neon_magenta).bg(Color::Rgb(20, 20, 30)))
+            .percent(mem_percent as u16)
+            .label(Span::styled(
+                format!("▓ MEM {:5.1}%", mem_percent),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ));
+        frame.render_widget(mem_gauge, *next.next().unwrap());
+    }
+
+    if supported.clocks {
+        render_clock_gauges(frame, *next.next().unwrap(), gpu, latest, theme);
+    }
+
+    if supported.fan {
+        let fan_pct = latest.fan_speed.unwrap_or(0.0);
+        let fan_gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default().bg(theme.dark_bg)),
+            )
+            .gauge_style(Style::default().fg(theme.neon_cyan).bg(Color::Rgb(20, 20, 30)))
+            .percent(fan_pct.clamp(0.0, 100.0) as u16)
+            .label(Span::styled(
+                format!("▓ FAN {:5.1}%", fan_pct),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ));
+        frame.render_widget(fan_gauge, *next.next().unwrap());
+    }
+
+    if show_chart {
+        render_metrics_chart(frame, *next.next().unwrap(), &data, supported, theme);
+    }
+
+    if show_processes {
+        render_process_table(frame, *next.next().unwrap(), gpu, process_view, is_selected, theme);
+    }
+}
+
+/// SM/memory clock gauges, shown as a percentage of the device's max clock
+/// when known (NVML/sysfs both report it); splits into two side-by-side
+/// gauges when both clocks are present, otherwise uses the full width.
+fn render_clock_gauges(frame: &mut Frame, area: Rect, gpu: &GpuInfo, latest: &GpuDataPoint, theme: &Theme) {
+    let cols: Vec<Constraint> = match (latest.sm_clock, latest.mem_clock) {
+        (Some(_), Some(_)) => vec![Constraint::Percentage(50), Constraint::Percentage(50)],
+        _ => vec![Constraint::Percentage(100)],
+    };
+    let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(inner[3]);
+        .constraints(cols)
+        .split(area);
+    let mut next = chunks.iter();
+
+    if let Some(sm_clock) = latest.sm_clock {
+        let pct = gpu
+            .max_sm_clock
+            .filter(|max| *max > 0.0)
+            .map(|max| (sm_clock / max * 100.0).clamp(0.0, 100.0))
+            .unwrap_or(0.0);
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default().bg(theme.dark_bg)),
+            )
+            .gauge_style(Style::default().fg(theme.neon_yellow).bg(Color::Rgb(20, 20, 30)))
+            .percent(pct as u16)
+            .label(Span::styled(
+                format!("▓ SM {:.0}MHz", sm_clock),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ));
+        frame.render_widget(gauge, *next.next().unwrap());
+    }
+
+    if let Some(mem_clock) = latest.mem_clock {
+        let pct = gpu
+            .max_mem_clock
+            .filter(|max| *max > 0.0)
+            .map(|max| (mem_clock / max * 100.0).clamp(0.0, 100.0))
+            .unwrap_or(0.0);
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default().bg(theme.dark_bg)),
+            )
+            .gauge_style(Style::default().fg(theme.cyber_blue).bg(Color::Rgb(20, 20, 30)))
+            .percent(pct as u16)
+            .label(Span::styled(
+                format!("▓ MEM {:.0}MHz", mem_clock),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ));
+        if let Some(target) = next.next() {
+            frame.render_widget(gauge, *target);
+        }
+    }
+}
+
+/// Scrolling time-series chart overlaying util/temp/power, replacing the old
+/// fixed-height sparklines so power (which can exceed 100) renders correctly
+/// and trends are readable against real time/value axes.
+fn render_metrics_chart(
+    frame: &mut Frame,
+    area: Rect,
+    data: &[&GpuDataPoint],
+    supported: SupportedMetrics,
+    theme: &Theme,
+) {
+    let Some(latest) = data.last() else {
+        return;
+    };
+    let now_ts = latest.timestamp;
+    let window_secs = data
+        .first()
+        .map(|dp| (now_ts - dp.timestamp).num_seconds().max(1))
+        .unwrap_or(60);
+
+    let util_points: Vec<(f64, f64)> = data
+        .iter()
+        .map(|dp| ((dp.timestamp - now_ts).num_milliseconds() as f64 / 1000.0, dp.gpu_util))
+        .collect();
+    let temp_points: Vec<(f64, f64)> = data
+        .iter()
+        .map(|dp| ((dp.timestamp - now_ts).num_milliseconds() as f64 / 1000.0, dp.temperature))
+        .collect();
+    let power_points: Vec<(f64, f64)> = data
+        .iter()
+        .map(|dp| ((dp.timestamp - now_ts).num_milliseconds() as f64 / 1000.0, dp.power_usage))
+        .collect();
 
-    // GPU Util sparkline
-    let util_data: Vec<u64> = data.iter().map(|dp| dp.gpu_util as u64).collect();
-    let util_sparkline = Sparkline::default()
+    let mut y_min = f64::INFINITY;
+    let mut y_max = f64::NEG_INFINITY;
+    for (points, enabled) in [
+        (&util_points, supported.utilization),
+        (&temp_points, supported.temperature),
+        (&power_points, supported.power),
+    ] {
+        if !enabled {
+            continue;
+        }
+        for &(_, v) in points {
+            y_min = y_min.min(v);
+            y_max = y_max.max(v);
+        }
+    }
+    if !y_min.is_finite() {
+        return;
+    }
+    if (y_max - y_min) < 1.0 {
+        y_max = y_min + 1.0;
+    }
+    let y_pad = (y_max - y_min) * 0.1;
+    let y_lo = (y_min - y_pad).max(0.0);
+    let y_hi = y_max + y_pad;
+
+    let mut datasets = Vec::new();
+    if supported.utilization {
+        datasets.push(
+            Dataset::default()
+                .name("UTIL%")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.neon_green))
+                .data(&util_points),
+        );
+    }
+    if supported.temperature {
+        datasets.push(
+            Dataset::default()
+                .name("TEMP°C")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.neon_red))
+                .data(&temp_points),
+        );
+    }
+    if supported.power {
+        datasets.push(
+            Dataset::default()
+                .name("PWR W")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.neon_yellow))
+                .data(&power_points),
+        );
+    }
+
+    let chart = Chart::new(datasets)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(Color::Rgb(40, 80, 40)))
-                .title(Span::styled(" ◇ UTIL% ", Style::default().fg(NEON_GREEN)))
-                .style(Style::default().bg(DARK_BG)),
+                .title(Span::styled(" ◇ METRICS ", Style::default().fg(theme.neon_green)))
+                .style(Style::default().bg(theme.dark_bg)),
         )
-        .data(&util_data)
-        .style(Style::default().fg(MATRIX_GREEN))
-        .max(100);
-    frame.render_widget(util_sparkline, spark_chunks[0]);
-
-    // Temperature sparkline
-    let temp_data: Vec<u64> = data.iter().map(|dp| dp.temperature as u64).collect();
-    let temp_sparkline = Sparkline::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Rgb(80, 40, 40)))
-                .title(Span::styled(" ◇ TEMP°C ", Style::default().fg(NEON_RED)))
-                .style(Style::default().bg(DARK_BG)),
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([-(window_secs as f64), 0.0])
+                .labels(vec![
+                    Span::raw(format!("-{window_secs}s")),
+                    Span::raw("now"),
+                ]),
         )
-        .data(&temp_data)
-        .style(Style::default().fg(NEON_RED))
-        .max(100);
-    frame.render_widget(temp_sparkline, spark_chunks[1]);
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([y_lo, y_hi])
+                .labels(vec![
+                    Span::raw(format!("{y_lo:.0}")),
+                    Span::raw(format!("{:.0}", (y_lo + y_hi) / 2.0)),
+                    Span::raw(format!("{y_hi:.0}")),
+                ]),
+        );
+
+    frame.render_widget(chart, area);
+}
+
+/// Renders the scrollable process table for one GPU, sorted per `process_view`.
+fn render_process_table(
+    frame: &mut Frame,
+    area: Rect,
+    gpu: &GpuInfo,
+    process_view: &ProcessView,
+    is_selected: bool,
+    theme: &Theme,
+) {
+    let mut processes: Vec<&ProcessInfo> = gpu.processes.iter().collect();
+    process_view.sort.sort(&mut processes, process_view.sort_reverse);
+
+    let header = Row::new(vec![
+        Cell::from("PID"),
+        Cell::from("PROCESS"),
+        Cell::from("GPU MEM"),
+        Cell::from("TYPE"),
+    ])
+    .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let rows = processes.iter().enumerate().map(|(row_idx, proc)| {
+        let highlighted = is_selected && row_idx == process_view.selected_process;
+        let style = if highlighted {
+            Style::default().fg(Color::Black).bg(theme.neon_green)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Row::new(vec![
+            Cell::from(proc.pid.to_string()),
+            Cell::from(proc.name.clone()),
+            Cell::from(format!("{} MiB", proc.used_memory)),
+            Cell::from(proc.proc_type.label()),
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Min(10),
+            Constraint::Length(12),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.matrix_green))
+            .title(Span::styled(" ◇ PROCESSES ", Style::default().fg(theme.neon_green)))
+            .style(Style::default().bg(theme.dark_bg)),
+    );
+
+    // Selecting the row in the state scrolls the table to keep it visible.
+    let mut state = TableState::default().with_selected(is_selected.then_some(process_view.selected_process));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+/// Centered `dd` confirmation dialog before SIGKILL-ing a process.
+fn render_kill_dialog(frame: &mut Frame, area: Rect, pid: u32, theme: &Theme) {
+    let width = 42.min(area.width);
+    let height = 5.min(area.height);
+    let dialog_area = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let text = vec![
+        Line::from(vec![Span::styled(
+            format!("Kill process {pid}?"),
+            Style::default().fg(theme.neon_red).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "[y] confirm   [n/Esc] cancel",
+            Style::default().fg(Color::DarkGray),
+        )]),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double)
+        .border_style(Style::default().fg(theme.neon_red))
+        .style(Style::default().bg(theme.dark_bg))
+        .title(" CONFIRM KILL ");
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(Paragraph::new(text).block(block).alignment(Alignment::Center), dialog_area);
 }
@@ -0,0 +1,139 @@
+use chrono::{TimeDelta, Utc};
+use std::path::Path;
+use std::process::Command;
+
+use crate::amd::AmdBackend;
+use crate::intel::IntelBackend;
+use crate::models::{GpuInfo, Vendor};
+use crate::nvidia;
+
+/// Source of GPU samples, polled once per tick.
+///
+/// Implementations normalize whatever the underlying driver/tooling exposes
+/// into the common `GpuDataPoint`, so the rest of the app never has to care
+/// which vendor or backend the data came from.
+pub trait GpuBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Longest history window `App` lets a user scroll back to (`]` widen); also
+/// the backend's own in-memory retention cap, so widening the window can
+/// never ask for data a backend has already discarded.
+pub const MAX_HISTORY_MINUTES: i64 = 7 * 24 * 60;
+
+/// Keep only samples within the backend's retention window for each GPU.
+pub(crate) fn trim_history(gpus: &mut [GpuInfo], now: chrono::DateTime<Utc>) {
+    let cutoff = now - TimeDelta::try_minutes(MAX_HISTORY_MINUTES).unwrap_or_default();
+    for gpu in gpus.iter_mut() {
+        while let Some(front) = gpu.data_points.front() {
+            if front.timestamp < cutoff {
+                gpu.data_points.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Merges devices from every discovered vendor backend into one `Vec<GpuInfo>`.
+///
+/// Each vendor's backend owns a contiguous run of the merged list (its length
+/// remembered from the last tick) and polls straight into those entries, so
+/// per-device history (and index numbering) survives across ticks, including
+/// history the caller reloaded or cleared in between. Nothing extra is tagged
+/// since each `GpuInfo` already carries its own `vendor`.
+pub struct CompositeBackend {
+    sources: Vec<(Box<dyn GpuBackend>, usize)>,
+}
+
+impl CompositeBackend {
+    fn new(backends: Vec<Box<dyn GpuBackend>>) -> Self {
+        Self {
+            sources: backends.into_iter().map(|b| (b, 0)).collect(),
+        }
+    }
+}
+
+impl GpuBackend for CompositeBackend {
+    fn poll(&mut self, gpus: &mut Vec<GpuInfo>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut previous = std::mem::take(gpus).into_iter();
+        for (backend, owned) in self.sources.iter_mut() {
+            let mut own: Vec<GpuInfo> = previous.by_ref().take(*owned).collect();
+            // A vendor whose tooling isn't installed just contributes nothing
+            // this tick rather than taking every other vendor down with it.
+            let _ = backend.poll(&mut own);
+            *owned = own.len();
+            gpus.extend(own);
+        }
+        Ok(())
+    }
+}
+
+fn command_exists(cmd: &str) -> bool {
+    Command::new(cmd).arg("--help").output().is_ok()
+}
+
+/// True if `rocm-smi` runs, or if any `/sys/class/drm/*/device/gpu_busy_percent`
+/// node exists, which on Linux is how the amdgpu driver exposes utilization
+/// without rocm-smi.
+fn amd_available() -> bool {
+    command_exists("rocm-smi") || sysfs_amdgpu_present()
+}
+
+fn sysfs_amdgpu_present() -> bool {
+    let Ok(entries) = Path::new("/sys/class/drm").read_dir() else {
+        return false;
+    };
+    entries.flatten().any(|entry| entry.path().join("device/gpu_busy_percent").exists())
+}
+
+/// True if any `/sys/class/drm/*/device/vendor` node reports PCI vendor id `id`.
+fn sysfs_vendor_present(id: &str) -> bool {
+    let Ok(entries) = Path::new("/sys/class/drm").read_dir() else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        std::fs::read_to_string(entry.path().join("device/vendor"))
+            .map(|v| v.trim() == id)
+            .unwrap_or(false)
+    })
+}
+
+/// Intel integrated/discrete GPUs show up under sysfs with PCI vendor id
+/// `0x8086`; that's the cheapest signal without shelling out to intel_gpu_top.
+fn intel_available() -> bool {
+    sysfs_vendor_present("0x8086")
+}
+
+/// NVIDIA GPUs show up under sysfs with PCI vendor id `0x10de`. Checked the
+/// same way as AMD: true if `nvidia-smi` runs or that sysfs id is present, so
+/// only a machine with neither skips the NVIDIA backend (and its once-a-second
+/// `nvidia-smi` fallback) entirely.
+fn nvidia_available() -> bool {
+    command_exists("nvidia-smi") || sysfs_vendor_present("0x10de")
+}
+
+/// Detects available GPU vendors at startup (unless `forced` pins one) and
+/// builds a backend that normalizes every discovered device into `GpuInfo`.
+pub fn build_backend(forced: Option<Vendor>) -> Box<dyn GpuBackend> {
+    if let Some(vendor) = forced {
+        return match vendor {
+            Vendor::Nvidia => nvidia::build_backend(),
+            Vendor::Amd => Box::new(AmdBackend::new()),
+            Vendor::Intel => Box::new(IntelBackend::new()),
+        };
+    }
+
+    let mut backends: Vec<Box<dyn GpuBackend>> = Vec::new();
+    if nvidia_available() {
+        backends.push(nvidia::build_backend());
+    }
+    if amd_available() {
+        backends.push(Box::new(AmdBackend::new()));
+    }
+    if intel_available() {
+        backends.push(Box::new(IntelBackend::new()));
+    }
+
+    Box::new(CompositeBackend::new(backends))
+}